@@ -8,6 +8,7 @@ pub const DAYS_240_SECONDS: u64 = 20736000;
 pub const DAYS_360_SECONDS: u64 = 31104000;
 pub const DAYS_720_SECONDS: u64 = 62208000;
 pub const ONE_YEAR_SECONDS: u64 = 31536000;
+pub const ONE_DAY_SECONDS: u64 = 86400;
 
 pub const DAYS_30_APY: u64 = 1000;
 pub const DAYS_60_APY: u64 = 2000;
@@ -16,4 +17,11 @@ pub const DAYS_120_APY: u64 = 5000;
 pub const DAYS_180_APY: u64 = 6500;
 pub const DAYS_240_APY: u64 = 9000;
 pub const DAYS_360_APY: u64 = 14800;
-pub const DAYS_720_APY: u64 = 18000;
\ No newline at end of file
+pub const DAYS_720_APY: u64 = 18000;
+pub const LEADERBOARD_SIZE: u8 = 100;
+pub const MAX_BOOST_COLLECTIONS: u32 = 10;
+pub const MAX_UNSTAKE_ALL_POSITIONS: usize = 30;
+pub const MAX_POSITIONS_PER_ADDRESS: usize = 100;
+pub const MIN_ON_BEHALF_STAKE: u128 = 1_000_000;
+pub const POSITION_NFT_NAME: &str = "Rize Staking Position";
+pub const POSITION_NFT_SYMBOL: &str = "RIZEPOS";
@@ -1,30 +1,63 @@
 use crate::constants::{self};
 use crate::error::ContractError;
 use crate::msg::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiveMsg, StakerInfo, StakerListResponse, TestBalanceResponse,
-    Token1ForToken2PriceResponse, Token2ForToken1PriceResponse,
+    ApyCurveResponse, ApyPoint, BonusConfigResponse, BonusResponse, Boost, BoostCollectionResponse,
+    BoostCollectionsResponse, ClaimOptions, ClaimableBalanceResponse, ConfigResponse, CuratedListResponse,
+    CuratedTargetResponse, CurationTarget, DistributionWalletsResponse, ExecuteMsg, InfoResponse, InstantiateMsg,
+    LeaderboardResponse, LiquidPoolResponse, MembersAtLevelResponse, MembershipLevel, MembershipLevelResponse,
+    MembershipLevelsResponse, MergeUnlock, MergeUnlockResponse, MigrateMsg, OperatorGrantResponse,
+    OperatorGrantsResponse, OperatorPermission, PendingTransferResponse, Permit, PermitAction, PermitFeeCapResponse,
+    PermitNonceResponse, PositionApproval, PositionApprovalsResponse, PositionMetadata, QueryMsg, QuoteApyResponse,
+    RankResponse, ReceiveMsg, RewardDistribution, StakeChangedExecuteMsg, StakeChangedHookMsg, StakerInfo,
+    StakerListResponse,
+    TestBalanceResponse, Token1ForToken2PriceResponse, Token2ForToken1PriceResponse, TopUpPoliciesResponse, TopUpPolicy,
+    TopUpPolicyResponse, TotalPowerAtHeightResponse, VePowerResponse, VestingResponse, VestingSchedule,
+    VotingPowerAtHeightResponse,
+};
+use crate::state::{
+    BonusConfig, BonusInfo, BonusState, Config, CuratedTarget, DistributionWallets, LiquidPool, OperatorGrant, VePoint,
+    APY_CURVE, BONUS_CONFIG, BONUS_STAKERS, BONUS_STATE, BOOST_COLLECTIONS, BOOST_HOLDERS, CLAIMABLE_BALANCES,
+    COLLECTIONS, CONFIG, CURATED_TARGETS, CURATION_INDEX, CURATION_SHARE, DISTRIBUTION_WALLETS, HOOKS,
+    HOOKS_IGNORE_ERRORS, LIQUID_POOL, LIQUID_TOKEN, MEMBERSHIP_LEVELS, MERGE_UNLOCK, NFT_OPERATORS, NUM_TOKENS,
+    OPERATORS, PENDING_TRANSFERS, PERMIT_FEE_CAP, PERMIT_NONCES, POSITION_APPROVALS, POSITION_COUNT, POSITION_OWNERS,
+    RANKS, RANK_INDEX, RANK_STAKERS, STAKED_BALANCES, STAKED_TOTAL, STAKERS, STAKE_FUNDERS, TOP_UP_POLICIES,
+    USER_VE_POINTS, USER_VE_SLOPE_CHANGES, VESTING_FUNDERS, VE_POINTS, VE_SLOPE_CHANGES, VOTING_WEIGHTED,
+    WEIGHTED_BALANCES, WEIGHTED_TOTAL,
 };
-use crate::state::{Config, CONFIG, RANKS, RANK_STAKERS, STAKERS};
 use crate::util;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
-    QueryRequest, Response, StdResult, Storage, Uint128, WasmMsg, WasmQuery,
+    attr, from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, Event, MessageInfo,
+    Order, QueryRequest, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw20::{
-    BalanceResponse as CW20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Denom,
+    BalanceResponse as CW20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Denom, MinterResponse,
     TokenInfoResponse,
 };
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw721::{
+    AllNftInfoResponse, Approval, ApprovalResponse, ApprovalsResponse, ContractInfoResponse, Cw721QueryMsg,
+    Cw721ReceiveMsg, NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+};
 use cw_storage_plus::Bound;
-use cw_utils::maybe_addr;
+use cw_utils::{maybe_addr, must_pay, parse_reply_instantiate_data, Expiration};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
 
 // Version info, for migration info
 const CONTRACT_NAME: &str = "rize-staking";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const MULTIPLE: u128 = 10000u128;
+const HOOK_REPLY_ID: u64 = 1;
+const LIQUID_TOKEN_REPLY_ID: u64 = 2;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+// stakers looked at by one page of a filtered listing, matching or not
+const MAX_SCAN: usize = 300;
 ///////////////////////////////////////////////////////// this func is called for instantiating the contract //////////////////////////////////
 ///
 ///         input params: owner address
@@ -75,10 +108,84 @@ pub fn execute(
         ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
         ExecuteMsg::WithdrawReward { amount } => execute_withdraw_reward(deps, env, info, amount),
         ExecuteMsg::WithdrawStake { amount } => execute_withdraw_stake(deps, env, info, amount),
-        ExecuteMsg::ClaimReward { } => {
-            execute_claim_reward(deps, env, info)
+        ExecuteMsg::ClaimReward { distribution, owner, recipient, ids, max_amount } => {
+            let options = ClaimOptions { distribution: distribution.unwrap_or_default(), ids, max_amount };
+            execute_claim_reward(deps, env, info, options, owner, recipient)
         }
+        ExecuteMsg::ClaimAndSend { contract, msg } => execute_claim_and_send(deps, env, info, contract, msg),
+        ExecuteMsg::Compound { owner } => execute_compound(deps, env, info, owner),
+        ExecuteMsg::ClaimWithPermit { owner, permit } => execute_claim_with_permit(deps, env, info, owner, permit),
+        ExecuteMsg::UpdatePermitFeeCap { percent } => execute_update_permit_fee_cap(deps, info, percent),
         ExecuteMsg::Unstake { index, amount } => execute_unstake(deps, env, info, index, amount),
+        ExecuteMsg::UnstakeAndSend { id, amount, contract, msg } => {
+            execute_unstake_and_send(deps, env, info, id, amount, contract, msg)
+        }
+        ExecuteMsg::UnstakeAll { claim_rewards } => execute_unstake_all(deps, env, info, claim_rewards),
+        ExecuteMsg::UpdateBonusConfig { denom, rank_threshold } => {
+            execute_update_bonus_config(deps, info, denom, rank_threshold)
+        }
+        ExecuteMsg::DepositNativeBonus { } => execute_deposit_native_bonus(deps, info),
+        ExecuteMsg::UpdateDistributionWallets { charity_wallet, artists_wallet } => {
+            execute_update_distribution_wallets(deps, info, charity_wallet, artists_wallet)
+        }
+        ExecuteMsg::UpdateCurationShare { share } => execute_update_curation_share(deps, info, share),
+        ExecuteMsg::RegisterCollection { id, payout } => {
+            execute_register_collection(deps, info, id, payout)
+        }
+        ExecuteMsg::SetCuration { id, curation } => execute_set_curation(deps, env, info, id, curation),
+        ExecuteMsg::RegisterBoostCollection { contract, multiplier } => {
+            execute_register_boost_collection(deps, info, contract, multiplier)
+        }
+        ExecuteMsg::RemoveBoostCollection { contract } => {
+            execute_remove_boost_collection(deps, info, contract)
+        }
+        ExecuteMsg::RefreshBoost {} => execute_refresh_boost(deps, env, info),
+        ExecuteMsg::UpdateVotingWeighted { weighted } => execute_update_voting_weighted(deps, env, info, weighted),
+        ExecuteMsg::AddHook { addr } => execute_add_hook(deps, info, addr),
+        ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, info, addr),
+        ExecuteMsg::UpdateHooksIgnoreErrors { ignore_errors } => {
+            execute_update_hooks_ignore_errors(deps, info, ignore_errors)
+        }
+        ExecuteMsg::UpdateMembershipLevels { levels } => execute_update_membership_levels(deps, info, levels),
+        ExecuteMsg::TransferPosition { id, recipient } => {
+            execute_transfer_position(deps, env, info, id, recipient)
+        }
+        ExecuteMsg::AcceptPosition { id } => execute_accept_position(deps, env, info, id),
+        ExecuteMsg::CancelPositionTransfer { id } => execute_cancel_position_transfer(deps, info, id),
+        ExecuteMsg::ApprovePosition { id, spender, expires } => {
+            execute_approve_position(deps, env, info, id, spender, expires)
+        }
+        ExecuteMsg::RevokePosition { id, spender } => execute_revoke_position(deps, env, info, id, spender),
+        ExecuteMsg::CreateLiquidToken { code_id, name, symbol, apy } => {
+            execute_create_liquid_token(deps, env, info, code_id, name, symbol, apy)
+        }
+        ExecuteMsg::SplitPosition { id, amount } => execute_split_position(deps, info, id, amount),
+        ExecuteMsg::MergePositions { ids } => execute_merge_positions(deps, env, info, ids),
+        ExecuteMsg::UpdateMergeUnlock { mode } => execute_update_merge_unlock(deps, info, mode),
+        ExecuteMsg::ExtendLock { id, new_tier } => execute_extend_lock(deps, env, info, id, new_tier),
+        ExecuteMsg::UpdateTopUpPolicy { tier, policy } => execute_update_top_up_policy(deps, info, tier, policy),
+        ExecuteMsg::UpdateApyCurve { points } => execute_update_apy_curve(deps, info, points),
+        ExecuteMsg::UpdateStakeFunder { funder, authorized } => {
+            execute_update_stake_funder(deps, info, funder, authorized)
+        }
+        ExecuteMsg::UpdateVestingFunder { funder, authorized } => {
+            execute_update_vesting_funder(deps, info, funder, authorized)
+        }
+        ExecuteMsg::GrantOperator { operator, permissions, expires } => {
+            execute_grant_operator(deps, env, info, operator, permissions, expires)
+        }
+        ExecuteMsg::RevokeOperator { operator } => execute_revoke_operator(deps, info, operator),
+        ExecuteMsg::MigrateBatch { start_after, limit } => execute_migrate_batch(deps, env, info, start_after, limit),
+        ExecuteMsg::TransferNft { recipient, token_id } => execute_transfer_nft(deps, env, info, recipient, token_id),
+        ExecuteMsg::SendNft { contract, token_id, msg } => execute_send_nft(deps, env, info, contract, token_id, msg),
+        ExecuteMsg::Approve { spender, token_id, expires } => {
+            execute_approve_position(deps, env, info, parse_token_id(&token_id)?, Addr::unchecked(spender), expires)
+        }
+        ExecuteMsg::Revoke { spender, token_id } => {
+            execute_revoke_position(deps, env, info, parse_token_id(&token_id)?, Addr::unchecked(spender))
+        }
+        ExecuteMsg::ApproveAll { operator, expires } => execute_approve_all(deps, env, info, operator, expires),
+        ExecuteMsg::RevokeAll { operator } => execute_revoke_all(deps, info, operator),
     }
 }
 ///////////////////////////////////////////////////////// this func is called when user click stake button on the frontend //////////////////////////////////
@@ -102,42 +209,98 @@ pub fn execute_receive(
     }
     let user_addr = &deps.api.addr_validate(&wrapper.sender)?;
 
-    if info.sender.clone() != cfg.rize_token_address {
+    let liquid_token = LIQUID_TOKEN.may_load(deps.storage)?;
+    let from_liquid_token = liquid_token.as_ref() == Some(&info.sender);
+    if info.sender.clone() != cfg.rize_token_address && !from_liquid_token {
         return Err(ContractError::UnacceptableToken {});
     }
 
     let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    // stRIZE is only accepted for redeeming, and redeeming only accepts stRIZE
+    if from_liquid_token != matches!(msg, ReceiveMsg::Redeem {}) {
+        return Err(ContractError::UnacceptableToken {});
+    }
     match msg {
-        ReceiveMsg::Stake { lock_type } => {
-            let mut list = STAKERS
-                .load(deps.storage, user_addr.clone())
-                .unwrap_or(vec![]);
-            list.push(StakerInfo {
-                address: user_addr.clone(),
-                amount: wrapper.amount,
-                reward: Uint128::zero(),
-                last_time: env.block.time.seconds(),
-                lock_type: match lock_type {
-                    0 => constants::DAYS_30_SECONDS,
-                    1 => constants::DAYS_60_SECONDS,
-                    2 => constants::DAYS_90_SECONDS,
-                    3 => constants::DAYS_120_SECONDS,
-                    4 => constants::DAYS_180_SECONDS,
-                    5 => constants::DAYS_240_SECONDS,
-                    6 => constants::DAYS_360_SECONDS,
-                    _ => constants::DAYS_720_SECONDS,
+        ReceiveMsg::Stake { lock_type, lock_seconds: custom_lock, curation, beneficiary, unstake_not_before } => {
+            // the position belongs to the beneficiary, the sender of the tokens is kept as funder
+            let staker_addr = &match beneficiary {
+                Some(beneficiary) => deps.api.addr_validate(beneficiary.as_str())?,
+                None => user_addr.clone(),
+            };
+            // positions pushed onto someone else come from allowed funders only and are not dust
+            if staker_addr != user_addr {
+                if !STAKE_FUNDERS.has(deps.storage, user_addr.clone()) {
+                    return Err(ContractError::Unauthorized {});
+                }
+                if wrapper.amount < Uint128::new(constants::MIN_ON_BEHALF_STAKE) {
+                    return Err(ContractError::BelowMinimumStake {});
+                }
+            }
+            // a funder can hold the principal for no longer than the longest tier
+            let now = env.block.time.seconds();
+            if unstake_not_before.is_some_and(|time| time > now + constants::DAYS_720_SECONDS) {
+                return Err(ContractError::InvalidInput {});
+            }
+
+            // a custom duration gets its apy from the curve, a tier keeps the apy of constants.rs
+            let (lock, apy) = match custom_lock {
+                Some(seconds) => (seconds, Some(quote_apy(&apy_curve(deps.storage)?, seconds)?)),
+                None => (lock_seconds(lock_type), None),
+            };
+
+            let curation = curation.map(|target| validate_curation(deps.as_ref(), target)).transpose()?;
+            if let Some(target) = &curation {
+                update_curated_stake(deps.storage, target, wrapper.amount, Uint128::zero())?;
+            }
+
+            let position = add_position(
+                deps.storage,
+                &env,
+                StakerInfo {
+                    id: 0,
+                    address: staker_addr.clone(),
+                    amount: wrapper.amount,
+                    reward: Uint128::zero(),
+                    last_time: env.block.time.seconds(),
+                    lock_type: lock,
+                    curation: curation.clone(),
+                    boost: None,
+                    funder: Some(user_addr.clone()),
+                    unstake_not_before,
+                    vesting: None,
+                    apy,
+                    settled_time: None,
+                    locked_reward: Uint128::zero(),
                 },
-            });
-            STAKERS.save(deps.storage, user_addr.clone(), &list)?;
+            )?;
+            let id = position.id;
 
             cfg.stake_amount = cfg.stake_amount + wrapper.amount;
             CONFIG.save(deps.storage, &cfg)?;
 
-            return Ok(Response::new().add_attributes(vec![
-                attr("action", "stake"),
-                attr("address", user_addr.clone()),
-                attr("amount", wrapper.amount),
-            ]));
+            let hooks = prepare_stake_hooks(
+                deps.storage,
+                StakeChangedHookMsg::Stake {
+                    addr: staker_addr.clone(),
+                    amount: wrapper.amount,
+                    lock_type: position.lock_type,
+                },
+            )?;
+
+            return Ok(Response::new()
+                .add_submessages(hooks)
+                .add_event(mint_event(user_addr, staker_addr, id))
+                .add_attributes(vec![
+                    attr("action", "stake"),
+                    attr("address", staker_addr.clone()),
+                    attr("funder", user_addr.clone()),
+                    attr("beneficiary", staker_addr.clone()),
+                    attr("id", id.to_string()),
+                    attr("amount", wrapper.amount),
+                    attr("lock_seconds", lock.to_string()),
+                    attr("apy", position_apy(&position).to_string()),
+                    attr("curation", curation.as_ref().map_or("none".to_string(), curation_key)),
+                ]));
         },
         ReceiveMsg::DepositReward { } => {
             //Just receive in contract cache and update config
@@ -151,7 +314,237 @@ pub fn execute_receive(
                 attr("amount", wrapper.amount),
             ]));
         }
+        ReceiveMsg::IncreasePosition { id } => {
+            let mut list = STAKERS.load(deps.storage, user_addr.clone())?;
+            let i = find_position(&list, id)?;
+            if list[i].vesting.is_some() {
+                return Err(ContractError::InvalidInput {});
+            }
+            let now = env.block.time.seconds();
+
+            let policy = TOP_UP_POLICIES
+                .may_load(deps.storage, list[i].lock_type)?
+                .unwrap_or_default();
+            match policy {
+                TopUpPolicy::Reset => {
+                    // reward so far is settled before the whole position is locked again from now
+                    settle_position(&mut list[i], now);
+                    list[i].last_time = now;
+                }
+                TopUpPolicy::WeightedAverage => {
+                    // reward so far is settled, only the unlock time moves to the amount weighted start
+                    settle_position(&mut list[i], now);
+                    let start = running_lock_start(&list[i], now);
+                    list[i].last_time = weighted_time(list[i].amount, start, wrapper.amount, now);
+                }
+            }
+            list[i].amount += wrapper.amount;
+            if let Some(target) = &list[i].curation {
+                update_curated_stake(deps.storage, target, wrapper.amount, Uint128::zero())?;
+            }
+            let position = list[i].clone();
+            STAKERS.save(deps.storage, user_addr.clone(), &list)?;
+            update_staker_aggregates(deps.storage, &env, user_addr.clone())?;
+
+            cfg.stake_amount += wrapper.amount;
+            CONFIG.save(deps.storage, &cfg)?;
+
+            let hooks = prepare_stake_hooks(
+                deps.storage,
+                StakeChangedHookMsg::Stake {
+                    addr: user_addr.clone(),
+                    amount: wrapper.amount,
+                    lock_type: position.lock_type,
+                },
+            )?;
+
+            Ok(Response::new().add_submessages(hooks).add_attributes(vec![
+                attr("action", "increase_position"),
+                attr("address", user_addr),
+                attr("id", id.to_string()),
+                attr("amount", wrapper.amount),
+                attr("total_amount", position.amount),
+                attr("unlock_time", (position.last_time + position.lock_type).to_string()),
+            ]))
+        }
+        ReceiveMsg::GrantVesting { beneficiary, lock_type, cliff, duration } => {
+            if !VESTING_FUNDERS.has(deps.storage, user_addr.clone()) {
+                return Err(ContractError::Unauthorized {});
+            }
+            if duration == 0 || cliff > duration {
+                return Err(ContractError::InvalidInput {});
+            }
+            let beneficiary = deps.api.addr_validate(beneficiary.as_str())?;
+
+            // the tier only sets the apy, the schedule decides what can be unstaked
+            let now = env.block.time.seconds();
+            let position = add_position(
+                deps.storage,
+                &env,
+                StakerInfo {
+                    id: 0,
+                    address: beneficiary.clone(),
+                    amount: wrapper.amount,
+                    reward: Uint128::zero(),
+                    last_time: now,
+                    lock_type: lock_seconds(lock_type),
+                    curation: None,
+                    boost: None,
+                    funder: Some(user_addr.clone()),
+                    unstake_not_before: None,
+                    vesting: Some(VestingSchedule {
+                        total: wrapper.amount,
+                        start: now,
+                        cliff,
+                        duration,
+                        released: Uint128::zero(),
+                    }),
+                    apy: None,
+                    settled_time: None,
+                    locked_reward: Uint128::zero(),
+                },
+            )?;
+
+            cfg.stake_amount += wrapper.amount;
+            CONFIG.save(deps.storage, &cfg)?;
+
+            let hooks = prepare_stake_hooks(
+                deps.storage,
+                StakeChangedHookMsg::Stake {
+                    addr: beneficiary.clone(),
+                    amount: wrapper.amount,
+                    lock_type: position.lock_type,
+                },
+            )?;
+
+            Ok(Response::new()
+                .add_submessages(hooks)
+                .add_event(mint_event(user_addr, &beneficiary, position.id))
+                .add_attributes(vec![
+                    attr("action", "grant_vesting"),
+                    attr("funder", user_addr),
+                    attr("beneficiary", beneficiary),
+                    attr("id", position.id.to_string()),
+                    attr("amount", wrapper.amount),
+                    attr("cliff_end", (now + cliff).to_string()),
+                    attr("vesting_end", (now + duration).to_string()),
+                ]))
+        }
+        ReceiveMsg::LiquidStake {} => {
+            let token = liquid_token.ok_or(ContractError::LiquidTokenNotConfigured {})?;
+            let mut pool = accrue_liquid_pool(LIQUID_POOL.load(deps.storage)?, env.block.time.seconds());
+
+            // one for one while the rate is 1, later stakers get less stRIZE for the same RIZE
+            let minted = wrapper.amount.multiply_ratio(Decimal::one().atomics(), pool.rate.atomics());
+            if minted.is_zero() {
+                return Err(ContractError::InvalidInput {});
+            }
+            pool.supply += minted;
+            pool.principal += wrapper.amount;
+            LIQUID_POOL.save(deps.storage, &pool)?;
+
+            cfg.stake_amount += wrapper.amount;
+            CONFIG.save(deps.storage, &cfg)?;
+
+            let mint = WasmMsg::Execute {
+                contract_addr: token.into(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: user_addr.into(),
+                    amount: minted,
+                })?,
+                funds: vec![],
+            };
+
+            // liquid stake has no lock, so it is reported with a zero lock_type
+            let hooks = prepare_stake_hooks(
+                deps.storage,
+                StakeChangedHookMsg::Stake {
+                    addr: user_addr.clone(),
+                    amount: wrapper.amount,
+                    lock_type: 0,
+                },
+            )?;
+
+            Ok(Response::new().add_message(mint).add_submessages(hooks).add_attributes(vec![
+                attr("action", "liquid_stake"),
+                attr("address", user_addr),
+                attr("amount", wrapper.amount),
+                attr("minted", minted),
+                attr("rate", pool.rate.to_string()),
+            ]))
+        }
+        ReceiveMsg::Redeem {} => {
+            let mut pool = accrue_liquid_pool(LIQUID_POOL.load(deps.storage)?, env.block.time.seconds());
+            if wrapper.amount > pool.supply {
+                return Err(ContractError::NotEnoughStake {});
+            }
+
+            // the principal share comes back from the stake, the growth of the rate is paid from the reward pool
+            let value = wrapper.amount * pool.rate;
+            let principal = pool.principal.multiply_ratio(wrapper.amount, pool.supply);
+            // a short pool pays what it holds, the rest is claimable once the pool is refilled
+            let accrued = value.saturating_sub(principal);
+            let reward = accrued.min(cfg.reward_amount);
+            let shortfall = accrued - reward;
+            credit_claimable(deps.storage, user_addr.clone(), shortfall)?;
+
+            pool.supply -= wrapper.amount;
+            pool.principal -= principal;
+            LIQUID_POOL.save(deps.storage, &pool)?;
+
+            cfg.stake_amount -= principal;
+            cfg.reward_amount -= reward;
+            CONFIG.save(deps.storage, &cfg)?;
+
+            let burn = WasmMsg::Execute {
+                contract_addr: info.sender.into(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn { amount: wrapper.amount })?,
+                funds: vec![],
+            };
+            let payout = WasmMsg::Execute {
+                contract_addr: cfg.rize_token_address.into(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: user_addr.clone().into(),
+                    amount: principal + reward,
+                })?,
+                funds: vec![],
+            };
+
+            let hooks = prepare_stake_hooks(
+                deps.storage,
+                StakeChangedHookMsg::Unstake {
+                    addr: user_addr.clone(),
+                    amount: principal,
+                },
+            )?;
+
+            Ok(Response::new().add_message(burn).add_message(payout).add_submessages(hooks).add_attributes(vec![
+                attr("action", "redeem"),
+                attr("address", user_addr),
+                attr("amount", wrapper.amount),
+                attr("principal", principal),
+                attr("reward", reward),
+                attr("shortfall", shortfall),
+                attr("rate", pool.rate.to_string()),
+            ]))
+        }
+    }
+}
+///////////////////////////////////////////////////////// this func is called for growing the stRIZE exchange rate up to now  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn accrue_liquid_pool(mut pool: LiquidPool, now: u64) -> LiquidPool {
+    if now > pool.last_time {
+        let growth = Decimal::from_ratio(
+            pool.apy as u128 * (now - pool.last_time) as u128,
+            MULTIPLE * constants::ONE_YEAR_SECONDS as u128,
+        );
+        pool.rate = pool.rate + pool.rate * growth;
+        pool.last_time = now;
     }
+    pool
 }
 ///////////////////////////////////////////////////////// this func is called for calculating the reward amount  //////////////////////////////////
 ///
@@ -167,233 +560,2349 @@ pub fn update_reward(
     let cfg = CONFIG.load(storage)?;
     let mut total_reward = Uint128::zero();
 
-    for i in 0..exists.len() {
-        let staked_time = env.block.time.seconds() - exists[i].last_time;
-        let mut reward_tot = Uint128::zero();
+    for staker in exists.iter_mut() {
+        mature_position(staker, cfg.reward_interval, env.block.time.seconds());
+        total_reward += staker.reward;
+    }
 
-        if staked_time < exists[i].lock_type {
-            reward_tot = Uint128::zero();
-        } else {
-            match exists[i].lock_type {
-                constants::DAYS_30_SECONDS => {
-                    if staked_time >= constants::DAYS_30_SECONDS {
-                        // 10.25% for over 30 days
-                        reward_tot = exists[i].amount * Uint128::from(constants::DAYS_30_APY)
-                            / Uint128::from(MULTIPLE);
-                    }
+    STAKERS.save(storage, address.clone(), &exists).unwrap();
+
+    return Ok(total_reward);
+}
+// pays a matured position for the time it was not settled and locks it again from now, others are left as they are
+fn mature_position(staker: &mut StakerInfo, reward_interval: u64, now: u64) {
+    let staked_time = now - staker.last_time;
+    // the lock matures on staked_time, the reward only counts what was not settled yet
+    let unsettled_time = now - reward_start(staker);
+    let mut reward_tot = Uint128::zero();
+    // what earlier settlements kept back until the lock ran out
+    if staked_time >= staker.lock_type {
+        staker.reward += staker.locked_reward;
+        staker.locked_reward = Uint128::zero();
+    }
+
+    if staked_time < staker.lock_type {
+        reward_tot = Uint128::zero();
+    } else if let Some(apy) = staker.apy {
+        // custom lock durations keep the apy quoted when they were staked
+        reward_tot = staker.amount * Uint128::from(apy) / Uint128::from(MULTIPLE);
+        staker.last_time = now;
+    } else {
+        match staker.lock_type {
+            constants::DAYS_30_SECONDS => {
+                if staked_time >= constants::DAYS_30_SECONDS {
+                    // 10.25% for over 30 days
+                    reward_tot = staker.amount * Uint128::from(constants::DAYS_30_APY)
+                        / Uint128::from(MULTIPLE);
                 }
-                constants::DAYS_60_SECONDS => {
-                    if staked_time >= constants::DAYS_60_SECONDS {
-                        // 10.25% for over 30 days
-                        reward_tot = exists[i].amount * Uint128::from(constants::DAYS_60_APY)
-                            / Uint128::from(MULTIPLE);
-                    }
+            }
+            constants::DAYS_60_SECONDS => {
+                if staked_time >= constants::DAYS_60_SECONDS {
+                    // 10.25% for over 30 days
+                    reward_tot = staker.amount * Uint128::from(constants::DAYS_60_APY)
+                        / Uint128::from(MULTIPLE);
                 }
-                constants::DAYS_90_SECONDS => {
-                    if staked_time >= constants::DAYS_90_SECONDS {
-                        // 10.25% for over 30 days
-                        reward_tot = exists[i].amount * Uint128::from(constants::DAYS_90_APY)
-                            / Uint128::from(MULTIPLE);
-                    }
+            }
+            constants::DAYS_90_SECONDS => {
+                if staked_time >= constants::DAYS_90_SECONDS {
+                    // 10.25% for over 30 days
+                    reward_tot = staker.amount * Uint128::from(constants::DAYS_90_APY)
+                        / Uint128::from(MULTIPLE);
                 }
-                constants::DAYS_120_SECONDS => {
-                    if staked_time >= constants::DAYS_120_SECONDS {
-                        // 10.25% for over 30 days
-                        reward_tot = exists[i].amount * Uint128::from(constants::DAYS_120_APY)
-                            / Uint128::from(MULTIPLE);
-                    }
+            }
+            constants::DAYS_120_SECONDS => {
+                if staked_time >= constants::DAYS_120_SECONDS {
+                    // 10.25% for over 30 days
+                    reward_tot = staker.amount * Uint128::from(constants::DAYS_120_APY)
+                        / Uint128::from(MULTIPLE);
                 }
-                constants::DAYS_180_SECONDS => {
-                    if staked_time >= constants::DAYS_180_SECONDS {
-                        // 10.25% for over 30 days
-                        reward_tot = exists[i].amount * Uint128::from(constants::DAYS_180_APY)
-                            / Uint128::from(MULTIPLE);
-                    }
+            }
+            constants::DAYS_180_SECONDS => {
+                if staked_time >= constants::DAYS_180_SECONDS {
+                    // 10.25% for over 30 days
+                    reward_tot = staker.amount * Uint128::from(constants::DAYS_180_APY)
+                        / Uint128::from(MULTIPLE);
                 }
-                constants::DAYS_240_SECONDS => {
-                    if staked_time >= constants::DAYS_240_SECONDS {
-                        // 10.25% for over 30 days
-                        reward_tot = exists[i].amount * Uint128::from(constants::DAYS_240_APY)
-                            / Uint128::from(MULTIPLE);
-                    }
+            }
+            constants::DAYS_240_SECONDS => {
+                if staked_time >= constants::DAYS_240_SECONDS {
+                    // 10.25% for over 30 days
+                    reward_tot = staker.amount * Uint128::from(constants::DAYS_240_APY)
+                        / Uint128::from(MULTIPLE);
                 }
-                constants::DAYS_360_SECONDS => {
-                    if staked_time >= constants::DAYS_360_SECONDS {
-                        // 10.25% for over 30 days
-                        reward_tot = exists[i].amount * Uint128::from(constants::DAYS_360_APY)
-                            / Uint128::from(MULTIPLE);
-                    }
+            }
+            constants::DAYS_360_SECONDS => {
+                if staked_time >= constants::DAYS_360_SECONDS {
+                    // 10.25% for over 30 days
+                    reward_tot = staker.amount * Uint128::from(constants::DAYS_360_APY)
+                        / Uint128::from(MULTIPLE);
                 }
-                _ => {
-                    if staked_time >= constants::DAYS_720_SECONDS {
-                        // 10.25% for over 30 days
-                        reward_tot = exists[i].amount * Uint128::from(constants::DAYS_720_APY)
-                            / Uint128::from(MULTIPLE);
-                    }
+            }
+            _ => {
+                if staked_time >= constants::DAYS_720_SECONDS {
+                    // 10.25% for over 30 days
+                    reward_tot = staker.amount * Uint128::from(constants::DAYS_720_APY)
+                        / Uint128::from(MULTIPLE);
                 }
             }
-            exists[i].last_time = env.block.time.seconds();
         }
-        let reward = reward_tot * (Uint128::from(cfg.reward_interval))
-            / (Uint128::from(constants::ONE_YEAR_SECONDS));
-        exists[i].reward = reward * Uint128::from(staked_time) / Uint128::from(cfg.reward_interval); //for test
-        total_reward += exists[i].reward;
+        staker.last_time = now;
     }
-
-    STAKERS.save(storage, address.clone(), &exists).unwrap();
-
-    return Ok(total_reward);
+    let reward = reward_tot * (Uint128::from(reward_interval))
+        / (Uint128::from(constants::ONE_YEAR_SECONDS));
+    // added to what earlier settlements left on the position, claim clears it
+    staker.reward += reward * Uint128::from(unsettled_time) / Uint128::from(reward_interval); //for test
 }
-///////////////////////////////////////////////////////// this func is called when we click reward button on frontend//////////////////////////////////
-///
-///         input params: customer's wallet address
-///                       juno reward flag(This is true when rank is bigger than 500, if this is true, customer can get juno reward, if false, cutomer can get only hole reward)
-///                       artists wallet percent
-///                       burn wallet percent
-///                       charity wallet percent
-///                       my wallet percent = 100 - artists_percent - burn_percent - charity_percent
+///////////////////////////////////////////////////////// this func is called for calculating the weighted stake of a staker //////////////////////////////////
 ///
+///         
 ///     
-/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
-pub fn execute_claim_reward(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-) -> Result<Response, ContractError> {
-    check_enabled(&deps, &info)?;
-    let mut cfg = CONFIG.load(deps.storage)?;
-
-    let reward = update_reward(deps.storage, env.clone(), info.sender.clone()).unwrap();
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn staker_weight(list: &[StakerInfo]) -> Uint128 {
+    // amount scaled by lock duration, only a 720 days lock counts in full
+    list.iter().fold(Uint128::zero(), |total, staker| {
+        total + staker.amount.multiply_ratio(staker.lock_type, constants::DAYS_720_SECONDS)
+    })
+}
 
-    let mut list = STAKERS
-        .load(deps.storage, info.sender.clone())
-        .unwrap_or(vec![]);
+///////////////////////////////////////////////////////// this func is called whenever the staked amount or the lock of a staker changes //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn update_staker_aggregates(storage: &mut dyn Storage, env: &Env, address: Addr) -> Result<(), ContractError> {
+    update_rank(storage, address.clone())?;
+    update_voting_power(storage, env.block.height, address.clone())?;
+    update_ve(storage, env.block.time.seconds(), address)?;
+    Ok(())
+}
+///////////////////////////////////////////////////////// this func is called for recording the voting power history of a staker //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+fn update_voting_power(storage: &mut dyn Storage, height: u64, address: Addr) -> Result<(), ContractError> {
+    let list = STAKERS.load(storage, address.clone()).unwrap_or(vec![]);
+    let staked = list.iter().fold(Uint128::zero(), |total, staker| total + staker.amount);
+    let weighted = staker_weight(&list);
 
-    if reward == Uint128::zero() {
-        return Err(ContractError::NoReward {});
+    // both histories are kept so the weighting can be switched at query time
+    let old_staked = STAKED_BALANCES.may_load(storage, address.clone())?.unwrap_or_default();
+    if old_staked != staked {
+        STAKED_BALANCES.save(storage, address.clone(), &staked, height)?;
+        let total = STAKED_TOTAL.may_load(storage)?.unwrap_or_default();
+        STAKED_TOTAL.save(storage, &(total + staked - old_staked), height)?;
     }
-    if cfg.reward_amount < reward {
-        return Err(ContractError::NotEnoughReward {});
-    }
-
-    cfg.reward_amount -= Uint128::from(reward);
-    CONFIG.save(deps.storage, &cfg)?;
 
-    for i in 0..list.len() {
-        list[i].reward = Uint128::zero();
-        // list[i].last_time = env.block.time.seconds();
+    let old_weighted = WEIGHTED_BALANCES.may_load(storage, address.clone())?.unwrap_or_default();
+    if old_weighted != weighted {
+        WEIGHTED_BALANCES.save(storage, address, &weighted, height)?;
+        let total = WEIGHTED_TOTAL.may_load(storage)?.unwrap_or_default();
+        WEIGHTED_TOTAL.save(storage, &(total + weighted - old_weighted), height)?;
     }
-    STAKERS.save(deps.storage, info.sender.clone(), &list)?;
+    Ok(())
+}
 
-    let exec_cw20_transfer = WasmMsg::Execute {
-        contract_addr: cfg.rize_token_address.clone().into(),
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: info.sender.clone().into(),
-            amount: Uint128::from(reward),
-        })?,
-        funds: vec![],
+// walks a ve checkpoint forward to the given time through the scheduled slope changes after it
+fn ve_walk(
+    last: Option<(u64, VePoint)>,
+    changes: Vec<(u64, Uint128)>,
+    time: u64,
+) -> VePoint {
+    let (mut ts, mut point) = match last {
+        Some(last) => last,
+        None => return VePoint::default(),
     };
+    for (change_ts, slope) in changes {
+        point.bias = point.bias.saturating_sub(point.slope * Uint128::from(change_ts - ts));
+        point.slope = point.slope.saturating_sub(slope);
+        ts = change_ts;
+    }
+    point.bias = point.bias.saturating_sub(point.slope * Uint128::from(time - ts));
+    point
+}
 
-    // End
+fn total_ve_point(storage: &dyn Storage, time: u64) -> StdResult<VePoint> {
+    let last = VE_POINTS
+        .range(storage, None, Some(Bound::inclusive(time)), Order::Descending)
+        .next()
+        .transpose()?;
+    let start = last.as_ref().map_or(0, |(ts, _)| *ts);
+    let changes = VE_SLOPE_CHANGES
+        .range(storage, Some(Bound::exclusive(start)), Some(Bound::inclusive(time)), Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ve_walk(last, changes, time))
+}
 
-    return Ok(Response::new().add_message(exec_cw20_transfer).add_attributes(vec![
-        attr("action", "claim_reward"),
-        attr("address", info.sender.clone()),
-        attr("reward_amount", Uint128::from(reward)),
-    ]));
+fn user_ve_point(storage: &dyn Storage, address: &Addr, time: u64) -> StdResult<VePoint> {
+    let last = USER_VE_POINTS
+        .prefix(address.clone())
+        .range(storage, None, Some(Bound::inclusive(time)), Order::Descending)
+        .next()
+        .transpose()?;
+    let start = last.as_ref().map_or(0, |(ts, _)| *ts);
+    let changes = USER_VE_SLOPE_CHANGES
+        .prefix(address.clone())
+        .range(storage, Some(Bound::exclusive(start)), Some(Bound::inclusive(time)), Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ve_walk(last, changes, time))
 }
-///////////////////////////////////////////////////////// this func is called when we click unstake button on frontend//////////////////////////////////
+///////////////////////////////////////////////////////// this func is called for checkpointing the vote-escrow power of a staker //////////////////////////////////
 ///
-///         Using this function, we can unstake all staked token
-///         input params: none
 ///         
 ///     
-/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
-pub fn execute_unstake(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    index: u64,
-    amount: Uint128,
-) -> Result<Response, ContractError> {
-    check_enabled(&deps, &info)?;
-    let mut cfg = CONFIG.load(deps.storage)?;
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+fn update_ve(storage: &mut dyn Storage, now: u64, address: Addr) -> Result<(), ContractError> {
+    // bias is kept in amount x seconds, the power is bias / DAYS_720_SECONDS
+    let mut total = total_ve_point(storage, now)?;
+    let old = user_ve_point(storage, &address, now)?;
+    total.bias -= old.bias;
+    total.slope -= old.slope;
 
-    let mut list = STAKERS.load(deps.storage, info.sender.clone())?;
-    let i = index as usize;
-    if list.len() <= i {
-        return Err(ContractError::NoStaked {});
-    }
-    
-    if amount == Uint128::zero() {
-        return Err(ContractError::InvalidInput {});
-    }
-    if list[i].amount < amount {
-        return Err(ContractError::NotEnoughStake {});
-    }
-    if cfg.stake_amount < amount {
-        return Err(ContractError::NotEnoughStake {});
-    }
-    if env.block.time.seconds() - list[i].last_time < list[i].lock_type {
-        return Err(ContractError::StillLocked {});
+    let scheduled = USER_VE_SLOPE_CHANGES
+        .prefix(address.clone())
+        .range(storage, Some(Bound::exclusive(now)), None, Order::Ascending)
+        .collect::<StdResult<Vec<(u64, Uint128)>>>()?;
+    for (ts, slope) in scheduled {
+        let change = VE_SLOPE_CHANGES.load(storage, ts)? - slope;
+        if change.is_zero() {
+            VE_SLOPE_CHANGES.remove(storage, ts);
+        } else {
+            VE_SLOPE_CHANGES.save(storage, ts, &change)?;
+        }
+        USER_VE_SLOPE_CHANGES.remove(storage, (address.clone(), ts));
     }
 
-    cfg.stake_amount -= amount;
-    CONFIG.save(deps.storage, &cfg)?;
-
-    list[i].amount -= amount;
-    if list[i].amount == Uint128::zero() {
-        list.remove(index as usize);
+    // unlock times are rounded down to the day to bound the slope changes to walk
+    let list = STAKERS.load(storage, address.clone()).unwrap_or(vec![]);
+    let mut point = VePoint::default();
+    for staker in list.iter() {
+        let unlock = (staker.last_time + staker.lock_type) / constants::ONE_DAY_SECONDS * constants::ONE_DAY_SECONDS;
+        if unlock <= now {
+            continue;
+        }
+        point.bias += staker.amount * Uint128::from(unlock - now);
+        point.slope += staker.amount;
+        USER_VE_SLOPE_CHANGES.update(storage, (address.clone(), unlock), |slope| -> StdResult<_> {
+            Ok(slope.unwrap_or_default() + staker.amount)
+        })?;
+        VE_SLOPE_CHANGES.update(storage, unlock, |slope| -> StdResult<_> {
+            Ok(slope.unwrap_or_default() + staker.amount)
+        })?;
     }
 
-    STAKERS.save(deps.storage, info.sender.clone(), &list)?;
-    
-    let exec_cw20_transfer = WasmMsg::Execute {
-        contract_addr: cfg.rize_token_address.clone().into(),
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: info.sender.clone().into(),
-            amount: Uint128::from(amount),
-        })?,
-        funds: vec![],
-    };
+    total.bias += point.bias;
+    total.slope += point.slope;
+    USER_VE_POINTS.save(storage, (address, now), &point)?;
+    VE_POINTS.save(storage, now, &total)?;
+    Ok(())
+}
 
-    return Ok(Response::new().add_message(exec_cw20_transfer).add_attributes(vec![
-        attr("action", "unstake"),
-        attr("address", info.sender.clone()),
-        attr("unstake_amount", Uint128::from(amount)),
-    ]));
+///////////////////////////////////////////////////////// this func is called for notifying the registered hook contracts of a stake change //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn prepare_stake_hooks(storage: &dyn Storage, msg: StakeChangedHookMsg) -> StdResult<Vec<SubMsg>> {
+    let ignore_errors = HOOKS_IGNORE_ERRORS.may_load(storage)?.unwrap_or(false);
+    HOOKS.prepare_hooks(storage, |addr| {
+        let execute = WasmMsg::Execute {
+            contract_addr: addr.into(),
+            msg: to_binary(&StakeChangedExecuteMsg::StakeChangeHook(msg.clone()))?,
+            funds: vec![],
+        };
+        // a failing hook reverts the whole operation unless errors are ignored in reply
+        Ok(if ignore_errors {
+            SubMsg::reply_on_error(execute, HOOK_REPLY_ID)
+        } else {
+            SubMsg::new(execute)
+        })
+    })
 }
 
-///////////////////////////////////////////////////////// this func is called for checking ownership//////////////////////////////////
+///////////////////////////////////////////////////////// this func is called for saving a new position of a staker under a new id //////////////////////////////////
 ///
-///         Owner is set when contract is instantiated.
-///         Using this function, we can authorize the ownership
+///         
 ///     
-/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
-pub fn check_owner(deps: &DepsMut, info: &MessageInfo) -> Result<Response, ContractError> {
-    let cfg = CONFIG.load(deps.storage)?;
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn add_position(
+    storage: &mut dyn Storage,
+    env: &Env,
+    mut position: StakerInfo,
+) -> Result<StakerInfo, ContractError> {
+    let mut list = STAKERS.load(storage, position.address.clone()).unwrap_or(vec![]);
+    check_position_limit(&list)?;
 
-    if info.sender != cfg.owner {
-        return Err(ContractError::Unauthorized {});
+    position.id = next_position_id(storage)?;
+    mint_position_nft(storage, position.id, &position.address)?;
+    list.push(position.clone());
+    STAKERS.save(storage, position.address.clone(), &list)?;
+    update_staker_aggregates(storage, env, position.address.clone())?;
+    Ok(position)
+}
+
+// claims and unstakes walk every position of an address, so it holds a bounded number of them
+fn check_position_limit(list: &[StakerInfo]) -> Result<(), ContractError> {
+    if list.len() >= constants::MAX_POSITIONS_PER_ADDRESS {
+        return Err(ContractError::TooManyPositions {});
     }
-    Ok(Response::new().add_attribute("action", "check_owner"))
+    Ok(())
 }
-///////////////////////////////////////////////////////// this func is called for checking enable state//////////////////////////////////
-///
-///         Enable state is set when contract is instantiated.
-///         The default vale is true.
-///     
-/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
-pub fn check_enabled(deps: &DepsMut, _info: &MessageInfo) -> Result<Response, ContractError> {
-    let cfg = CONFIG.load(deps.storage)?;
-    if !cfg.enabled {
-        return Err(ContractError::Disabled {});
+// the part of a vesting grant that is unlocked at the given time
+pub fn vested_amount(schedule: &VestingSchedule, now: u64) -> Uint128 {
+    let elapsed = now.saturating_sub(schedule.start);
+    if elapsed < schedule.cliff {
+        return Uint128::zero();
     }
-    Ok(Response::new().add_attribute("action", "check_enabled"))
+    schedule
+        .total
+        .multiply_ratio(elapsed.min(schedule.duration), schedule.duration)
 }
-///////////////////////////////////////////////////////// this func is called for updating the ownership//////////////////////////////////
-///
+
+pub fn next_position_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    // ids start from 1, 0 is left for positions staked before ids existed
+    let id = POSITION_COUNT.may_load(storage)?.unwrap_or(0) + 1;
+    POSITION_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+fn find_position(list: &[StakerInfo], id: u64) -> Result<usize, ContractError> {
+    list.iter()
+        .position(|staker| staker.id == id)
+        .ok_or(ContractError::NoStaked {})
+}
+
+// the position nfts report what happens to them with the events cw721-base emits, so indexers follow them
+fn mint_event(minter: &Addr, owner: &Addr, id: u64) -> Event {
+    Event::new("mint").add_attributes(vec![
+        attr("action", "mint"),
+        attr("minter", minter),
+        attr("owner", owner),
+        attr("token_id", id.to_string()),
+    ])
+}
+
+fn transfer_event(sender: &Addr, recipient: &Addr, id: u64) -> Event {
+    Event::new("transfer_nft").add_attributes(vec![
+        attr("action", "transfer_nft"),
+        attr("sender", sender),
+        attr("recipient", recipient),
+        attr("token_id", id.to_string()),
+    ])
+}
+
+fn burn_event(sender: &Addr, id: u64) -> Event {
+    Event::new("burn").add_attributes(vec![
+        attr("action", "burn"),
+        attr("sender", sender),
+        attr("token_id", id.to_string()),
+    ])
+}
+
+fn mint_position_nft(storage: &mut dyn Storage, id: u64, owner: &Addr) -> StdResult<()> {
+    POSITION_OWNERS.save(storage, id, owner)?;
+    let count = NUM_TOKENS.may_load(storage)?.unwrap_or(0);
+    NUM_TOKENS.save(storage, &(count + 1))
+}
+
+fn remove_position_records(storage: &mut dyn Storage, id: u64) -> StdResult<()> {
+    POSITION_OWNERS.remove(storage, id);
+    let count = NUM_TOKENS.may_load(storage)?.unwrap_or(0);
+    NUM_TOKENS.save(storage, &count.saturating_sub(1))?;
+    PENDING_TRANSFERS.remove(storage, id);
+    clear_position_approvals(storage, id)
+}
+
+fn clear_position_approvals(storage: &mut dyn Storage, id: u64) -> StdResult<()> {
+    let spenders = POSITION_APPROVALS
+        .prefix(id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for spender in spenders {
+        POSITION_APPROVALS.remove(storage, (id, spender));
+    }
+    Ok(())
+}
+
+fn parse_token_id(token_id: &str) -> StdResult<u64> {
+    token_id
+        .parse::<u64>()
+        .map_err(|_| StdError::generic_err(format!("Invalid token id: {}", token_id)))
+}
+
+fn check_owner_or_operator(storage: &dyn Storage, env: &Env, sender: &Addr, owner: &Addr) -> Result<(), ContractError> {
+    if sender == owner {
+        return Ok(());
+    }
+    match NFT_OPERATORS.may_load(storage, (owner.clone(), sender.clone()))? {
+        Some(expires) if !expires.is_expired(&env.block) => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+fn check_can_transfer(storage: &dyn Storage, env: &Env, sender: &Addr, owner: &Addr, id: u64) -> Result<(), ContractError> {
+    if check_owner_or_operator(storage, env, sender, owner).is_ok() {
+        return Ok(());
+    }
+    match POSITION_APPROVALS.may_load(storage, (id, sender.clone()))? {
+        Some(expires) if !expires.is_expired(&env.block) => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+///////////////////////////////////////////////////////// this func is called for moving a position with its lock, tier and pending reward to another staker //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn transfer_position(
+    storage: &mut dyn Storage,
+    env: &Env,
+    id: u64,
+    recipient: Addr,
+) -> Result<(Addr, StakerInfo), ContractError> {
+    let owner = POSITION_OWNERS.load(storage, id).map_err(|_| ContractError::NoStaked {})?;
+    if owner == recipient {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    let mut list = STAKERS.load(storage, owner.clone())?;
+    let mut position = list.remove(find_position(&list, id)?);
+    STAKERS.save(storage, owner.clone(), &list)?;
+
+    // the boost belongs to the nfts of the former owner
+    position.address = recipient.clone();
+    position.boost = None;
+    let mut recipient_list = STAKERS.load(storage, recipient.clone()).unwrap_or(vec![]);
+    check_position_limit(&recipient_list)?;
+    recipient_list.push(position.clone());
+    STAKERS.save(storage, recipient.clone(), &recipient_list)?;
+
+    POSITION_OWNERS.save(storage, id, &recipient)?;
+    PENDING_TRANSFERS.remove(storage, id);
+    clear_position_approvals(storage, id)?;
+
+    update_staker_aggregates(storage, env, owner.clone())?;
+    update_staker_aggregates(storage, env, recipient)?;
+    Ok((owner, position))
+}
+
+// the lock_type index a position was staked with, none for a custom lock duration
+pub fn lock_tier(lock_seconds: u64) -> Option<u64> {
+    match lock_seconds {
+        constants::DAYS_30_SECONDS => Some(0),
+        constants::DAYS_60_SECONDS => Some(1),
+        constants::DAYS_90_SECONDS => Some(2),
+        constants::DAYS_120_SECONDS => Some(3),
+        constants::DAYS_180_SECONDS => Some(4),
+        constants::DAYS_240_SECONDS => Some(5),
+        constants::DAYS_360_SECONDS => Some(6),
+        constants::DAYS_720_SECONDS => Some(7),
+        _ => None,
+    }
+}
+
+// the lock duration of a lock_type index
+pub fn lock_seconds(tier: u64) -> u64 {
+    match tier {
+        0 => constants::DAYS_30_SECONDS,
+        1 => constants::DAYS_60_SECONDS,
+        2 => constants::DAYS_90_SECONDS,
+        3 => constants::DAYS_120_SECONDS,
+        4 => constants::DAYS_180_SECONDS,
+        5 => constants::DAYS_240_SECONDS,
+        6 => constants::DAYS_360_SECONDS,
+        _ => constants::DAYS_720_SECONDS,
+    }
+}
+
+// the apy of a lock duration, based on MULTIPLE
+pub fn tier_apy(lock_seconds: u64) -> u64 {
+    match lock_seconds {
+        constants::DAYS_30_SECONDS => constants::DAYS_30_APY,
+        constants::DAYS_60_SECONDS => constants::DAYS_60_APY,
+        constants::DAYS_90_SECONDS => constants::DAYS_90_APY,
+        constants::DAYS_120_SECONDS => constants::DAYS_120_APY,
+        constants::DAYS_180_SECONDS => constants::DAYS_180_APY,
+        constants::DAYS_240_SECONDS => constants::DAYS_240_APY,
+        constants::DAYS_360_SECONDS => constants::DAYS_360_APY,
+        _ => constants::DAYS_720_APY,
+    }
+}
+// the apy a position earns, custom lock durations carry their own
+pub fn position_apy(staker: &StakerInfo) -> u64 {
+    staker.apy.unwrap_or_else(|| tier_apy(staker.lock_type))
+}
+
+// the configured apy curve, by default a point for every tier of constants.rs
+pub fn apy_curve(storage: &dyn Storage) -> StdResult<Vec<ApyPoint>> {
+    Ok(APY_CURVE.may_load(storage)?.unwrap_or_else(|| {
+        (0..8)
+            .map(|tier| ApyPoint {
+                lock_seconds: lock_seconds(tier),
+                apy: tier_apy(lock_seconds(tier)),
+            })
+            .collect()
+    }))
+}
+
+// the apy of a lock duration, linear between the two control points around it
+pub fn quote_apy(points: &[ApyPoint], lock_seconds: u64) -> Result<u64, ContractError> {
+    let (first, last) = match (points.first(), points.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Err(ContractError::InvalidLockDuration {}),
+    };
+    if lock_seconds < first.lock_seconds || lock_seconds > last.lock_seconds {
+        return Err(ContractError::InvalidLockDuration {});
+    }
+
+    for pair in points.windows(2) {
+        let (from, to) = (&pair[0], &pair[1]);
+        if lock_seconds <= to.lock_seconds {
+            let offset = (lock_seconds - from.lock_seconds) as u128;
+            let span = (to.lock_seconds - from.lock_seconds) as u128;
+            // the curve may fall between two points as well as rise
+            let apy = if to.apy >= from.apy {
+                from.apy as u128 + (to.apy - from.apy) as u128 * offset / span
+            } else {
+                from.apy as u128 - (from.apy - to.apy) as u128 * offset / span
+            };
+            return Ok(apy as u64);
+        }
+    }
+    Ok(last.apy)
+}
+///////////////////////////////////////////////////////// this func is called for moving the reward a position earned so far into its pending reward //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn settle_position(staker: &mut StakerInfo, now: u64) {
+    // same rate update_reward pays on maturity, counted for the time not settled so far
+    let apy = position_apy(staker) as u128;
+    let staked_time = now.saturating_sub(reward_start(staker)) as u128;
+    let reward = staker
+        .amount
+        .multiply_ratio(apy * staked_time, MULTIPLE * constants::ONE_YEAR_SECONDS as u128);
+    // nothing is claimable before the lock runs out, settling early must not change that
+    if now.saturating_sub(staker.last_time) < staker.lock_type {
+        staker.locked_reward += reward;
+    } else {
+        staker.reward += staker.locked_reward + reward;
+        staker.locked_reward = Uint128::zero();
+    }
+    // the boost is credited by ownership checks only, the time since the last one is not verified
+    if let Some(boost) = &mut staker.boost {
+        boost.since = boost.since.max(now);
+    }
+    // the lock is left alone, callers restarting it set last_time themselves
+    staker.settled_time = Some(now);
+}
+
+// reward runs from the start of the lock or from the last settlement, whichever is later
+fn reward_start(staker: &StakerInfo) -> u64 {
+    staker.last_time.max(staker.settled_time.unwrap_or(0))
+}
+
+// the reward a boost adds on top of the apy of a position over the given time
+fn boost_reward(staker: &StakerInfo, multiplier: u64, seconds: u64) -> Uint128 {
+    staker.amount.multiply_ratio(
+        position_apy(staker) as u128 * (multiplier as u128 - MULTIPLE) * seconds as u128,
+        MULTIPLE * MULTIPLE * constants::ONE_YEAR_SECONDS as u128,
+    )
+}
+
+fn rank_key(weight: Uint128, address: Addr) -> (u128, Addr) {
+    // inverted so an ascending range walks from the heaviest stake, ties go to the lower address
+    (u128::MAX - weight.u128(), address)
+}
+///////////////////////////////////////////////////////// this func is called for updating the leaderboard whenever the positions of a staker change //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn update_rank(storage: &mut dyn Storage, address: Addr) -> Result<(), ContractError> {
+    // RANKS keeps the weighted stake of every staker and RANK_INDEX orders them,
+    // RANK_STAKERS caches the top LEADERBOARD_SIZE stakers keyed by rank starting from 1
+    let list = STAKERS.load(storage, address.clone()).unwrap_or(vec![]);
+    let weight = staker_weight(&list);
+    let old_weight = RANKS.may_load(storage, address.clone())?.unwrap_or_default();
+    if old_weight == weight {
+        return Ok(());
+    }
+
+    let cutoff = RANK_STAKERS
+        .may_load(storage, constants::LEADERBOARD_SIZE)?
+        .map(|(addr, weight)| rank_key(weight, addr));
+    let old_key = rank_key(old_weight, address.clone());
+    let new_key = rank_key(weight, address.clone());
+
+    if !old_weight.is_zero() {
+        RANK_INDEX.remove(storage, old_key.clone());
+    }
+    if weight.is_zero() {
+        RANKS.remove(storage, address.clone());
+    } else {
+        RANKS.save(storage, address.clone(), &weight)?;
+        RANK_INDEX.save(storage, new_key.clone(), &weight)?;
+    }
+
+    let affected = match cutoff {
+        None => true,
+        Some(cutoff) => {
+            (!old_weight.is_zero() && old_key <= cutoff) || (!weight.is_zero() && new_key <= cutoff)
+        }
+    };
+    // the cache only moves when the staker was or becomes part of it
+    if affected {
+        let changed = rebuild_leaderboard(storage)?;
+
+        let rank_threshold = BONUS_CONFIG
+            .may_load(storage)?
+            .map_or(0, |bonus_cfg| bonus_cfg.rank_threshold);
+        let addresses = changed
+            .into_iter()
+            .filter(|(rank, _)| *rank <= rank_threshold)
+            .map(|(_, addr)| addr)
+            .collect();
+        sync_bonus_eligibility(storage, addresses)?;
+    }
+    Ok(())
+}
+
+// returns the ranks whose entry changed, with both the former and the new staker of the rank
+fn rebuild_leaderboard(storage: &mut dyn Storage) -> Result<Vec<(u8, Addr)>, ContractError> {
+    let top: Vec<(Addr, Uint128)> = RANK_INDEX
+        .range(storage, None, None, Order::Ascending)
+        .take(constants::LEADERBOARD_SIZE as usize)
+        .map(|item| item.map(|((_, addr), weight)| (addr, weight)))
+        .collect::<StdResult<_>>()?;
+
+    let mut changed = vec![];
+    for rank in 1..=constants::LEADERBOARD_SIZE {
+        let current = RANK_STAKERS.may_load(storage, rank)?;
+        match top.get(rank as usize - 1) {
+            Some(entry) => {
+                if current.as_ref() != Some(entry) {
+                    RANK_STAKERS.save(storage, rank, entry)?;
+                    changed.push((rank, entry.0.clone()));
+                    if let Some((addr, _)) = current {
+                        changed.push((rank, addr));
+                    }
+                }
+            }
+            None => {
+                match current {
+                    Some((addr, _)) => changed.push((rank, addr)),
+                    None => break,
+                }
+                RANK_STAKERS.remove(storage, rank);
+            }
+        }
+    }
+    Ok(changed)
+}
+///////////////////////////////////////////////////////// this func is called for settling the native bonus of a staker //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+fn settle_bonus(storage: &dyn Storage, state: &BonusState, address: Addr) -> StdResult<BonusInfo> {
+    let mut bonus = BONUS_STAKERS.may_load(storage, address)?.unwrap_or_default();
+    bonus.pending += bonus.weight * (state.index - bonus.index);
+    bonus.index = state.index;
+    Ok(bonus)
+}
+///////////////////////////////////////////////////////// this func is called for updating the stakers who share the native bonus //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+fn sync_bonus_eligibility(storage: &mut dyn Storage, addresses: Vec<Addr>) -> Result<(), ContractError> {
+    let bonus_cfg = match BONUS_CONFIG.may_load(storage)? {
+        Some(bonus_cfg) => bonus_cfg,
+        None => return Ok(()),
+    };
+    let mut state = BONUS_STATE.load(storage)?;
+
+    // only stakers at or above the rank threshold share the bonus, by weighted stake
+    let eligible: Vec<(Addr, Uint128)> = RANK_STAKERS
+        .range(storage, None, Some(Bound::inclusive(bonus_cfg.rank_threshold)), Order::Ascending)
+        .map(|item| item.map(|(_, entry)| entry))
+        .collect::<StdResult<_>>()?;
+
+    for address in addresses {
+        let weight = eligible
+            .iter()
+            .find(|(addr, _)| *addr == address)
+            .map_or(Uint128::zero(), |(_, weight)| *weight);
+
+        let mut bonus = settle_bonus(storage, &state, address.clone())?;
+        state.eligible_weight = state.eligible_weight - bonus.weight + weight;
+        bonus.weight = weight;
+        BONUS_STAKERS.save(storage, address, &bonus)?;
+    }
+
+    // deposits made while nobody was eligible
+    if !state.undistributed.is_zero() && !state.eligible_weight.is_zero() {
+        state.index += Decimal::from_ratio(state.undistributed, state.eligible_weight);
+        state.undistributed = Uint128::zero();
+    }
+    BONUS_STATE.save(storage, &state)?;
+    Ok(())
+}
+///////////////////////////////////////////////////////// this func is called when we click reward button on frontend//////////////////////////////////
+///
+///         input params: customer's wallet address
+///                       (juno bonus is paid without a flag when the rank is at or above the bonus rank threshold)
+///                       artists wallet percent
+///                       burn wallet percent
+///                       charity wallet percent
+///                       my wallet percent = 100 - artists_percent - burn_percent - charity_percent
+///
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_claim_reward(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    options: ClaimOptions,
+    owner: Option<Addr>,
+    recipient: Option<Addr>,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let owner = operator_owner(deps.as_ref(), &env, &info.sender, owner, OperatorPermission::Claim)?;
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(recipient.as_str())?,
+        None => owner.clone(),
+    };
+    // operators only claim to the owner, never to other wallets
+    if owner != info.sender && (options.distribution != RewardDistribution::default() || recipient != owner) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let response = claim_reward(deps, env, owner, options, None, recipient, None)?;
+    Ok(response.add_attribute("sender", info.sender))
+}
+///////////////////////////////////////////////////////// this func is called when user pipes its reward into another contract //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_claim_and_send(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: Addr,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let contract = deps.api.addr_validate(contract.as_str())?;
+    let response = claim_reward(
+        deps,
+        env,
+        info.sender.clone(),
+        ClaimOptions::default(),
+        None,
+        contract,
+        Some(msg),
+    )?;
+    Ok(response.add_attribute("sender", info.sender))
+}
+///////////////////////////////////////////////////////// this func is called for settling and paying out the reward of a staker //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn claim_reward(
+    deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    options: ClaimOptions,
+    relayer_fee: Option<(Addr, Uint128)>,
+    recipient: Addr,
+    send_msg: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let mut cfg = CONFIG.load(deps.storage)?;
+    let distribution = options.distribution;
+
+    // every share is a percent on its own, the sum is checked so chosen values cannot wrap
+    if distribution.charity > 100 || distribution.burn > 100 || distribution.artists > 100 {
+        return Err(ContractError::InvalidDistribution {});
+    }
+    let total_percent = distribution
+        .charity
+        .checked_add(distribution.burn)
+        .and_then(|total| total.checked_add(distribution.artists))
+        .ok_or(ContractError::InvalidInput {})?;
+    if total_percent > 100 {
+        return Err(ContractError::InvalidDistribution {});
+    }
+    let wallets = DISTRIBUTION_WALLETS.may_load(deps.storage)?;
+    if wallets.is_none() && (distribution.charity > 0 || distribution.artists > 0) {
+        return Err(ContractError::DistributionNotConfigured {});
+    }
+
+    // every check that can refuse the claim runs before the first write, so a refused claim leaves storage as it was
+    let mut list = STAKERS
+        .load(deps.storage, owner.clone())
+        .unwrap_or(vec![]);
+
+    // a boost is credited up to now or dropped when the nft was sold
+    let verified = verify_boost(deps.as_ref(), &owner)?;
+    credit_boost(deps.storage, &owner, &mut list, &verified, env.block.time.seconds())?;
+
+    let settled_bonus = pending_bonus(deps.storage, owner.clone())?;
+    let bonus = settled_bonus
+        .as_ref()
+        .filter(|(_, settled)| !settled.pending.is_zero())
+        .map(|(denom, settled)| (denom.clone(), settled.pending));
+
+    // only the selected positions are settled and locked again, the others keep their reward and their lock
+    let mut selected = match &options.ids {
+        Some(ids) => ids.iter().map(|id| find_position(&list, *id)).collect::<Result<Vec<usize>, _>>()?,
+        None => (0..list.len()).collect(),
+    };
+    selected.sort_unstable();
+    selected.dedup();
+    for i in &selected {
+        mature_position(&mut list[*i], cfg.reward_interval, env.block.time.seconds());
+    }
+    let claimable = CLAIMABLE_BALANCES.may_load(deps.storage, owner.clone())?.unwrap_or_default();
+    let owed = selected.iter().fold(claimable, |total, i| total + list[*i].reward);
+
+    if owed == Uint128::zero() && bonus.is_none() {
+        return Err(ContractError::NoReward {});
+    }
+    // a short pool pays what it holds, the rest is carried on the positions
+    let mut reward = owed.min(cfg.reward_amount);
+    if let Some(max_amount) = options.max_amount {
+        reward = reward.min(max_amount);
+    }
+    if reward == Uint128::zero() && bonus.is_none() {
+        return Err(ContractError::NotEnoughReward {});
+    }
+
+    // curated positions send their share of the reward to the curated target first
+    let curation_share = CURATION_SHARE.may_load(deps.storage)?.unwrap_or(0);
+    let mut curation_amount = Uint128::zero();
+    let mut curated = vec![];
+    let mut response = Response::new();
+    // the claimable balance goes first, its curation share was taken when it was settled
+    let from_claimable = claimable.min(reward);
+    let mut to_pay = reward - from_claimable;
+    for i in selected {
+        let paid = list[i].reward.min(to_pay);
+        to_pay -= paid;
+        list[i].reward -= paid;
+        if let Some(target) = &list[i].curation {
+            let share = paid.multiply_ratio(curation_share, 100u128);
+            if share > Uint128::zero() {
+                response = response.add_message(util::transfer_token_message(
+                    Denom::Cw20(cfg.rize_token_address.clone()),
+                    share,
+                    curation_payout(deps.storage, target)?,
+                )?);
+                curated.push((target.clone(), share));
+                curation_amount += share;
+            }
+        }
+    }
+
+    // nothing below refuses the claim any more
+    if let Some((_, collection, token_id)) = verified {
+        BOOST_HOLDERS.save(deps.storage, (collection, token_id), &owner)?;
+    }
+    if let Some((_, mut settled)) = settled_bonus {
+        settled.pending = Uint128::zero();
+        BONUS_STAKERS.save(deps.storage, owner.clone(), &settled)?;
+    }
+    cfg.reward_amount -= Uint128::from(reward);
+    CONFIG.save(deps.storage, &cfg)?;
+    if from_claimable > Uint128::zero() {
+        CLAIMABLE_BALANCES.save(deps.storage, owner.clone(), &(claimable - from_claimable))?;
+    }
+    for (target, share) in curated {
+        pay_curated_target(deps.storage, &target, share)?;
+    }
+
+    STAKERS.save(deps.storage, owner.clone(), &list)?;
+    // claimed positions are locked again from now
+    update_staker_aggregates(deps.storage, &env, owner.clone())?;
+
+    // my wallet percent = 100 - charity percent - burn percent - artists percent
+    let reward_left = reward - curation_amount;
+    let charity_amount = reward_left.multiply_ratio(distribution.charity, 100u128);
+    let burn_amount = reward_left.multiply_ratio(distribution.burn, 100u128);
+    let artists_amount = reward_left.multiply_ratio(distribution.artists, 100u128);
+    let mut user_amount = reward_left - charity_amount - burn_amount - artists_amount;
+    // a relayer fee comes out of the owner's part, capped by the owner of the contract
+    let mut fee_amount = Uint128::zero();
+    if let Some((relayer, fee)) = relayer_fee {
+        let cap = PERMIT_FEE_CAP.may_load(deps.storage)?.unwrap_or(0);
+        fee_amount = fee.min(reward.multiply_ratio(cap, 100u128)).min(user_amount);
+        if fee_amount > Uint128::zero() {
+            response = response.add_message(util::transfer_token_message(
+                Denom::Cw20(cfg.rize_token_address.clone()),
+                fee_amount,
+                relayer,
+            )?);
+            user_amount -= fee_amount;
+        }
+    }
+    if let Some(wallets) = wallets {
+        if charity_amount > Uint128::zero() {
+            response = response.add_message(util::transfer_token_message(
+                Denom::Cw20(cfg.rize_token_address.clone()),
+                charity_amount,
+                wallets.charity_wallet,
+            )?);
+        }
+        if artists_amount > Uint128::zero() {
+            response = response.add_message(util::transfer_token_message(
+                Denom::Cw20(cfg.rize_token_address.clone()),
+                artists_amount,
+                wallets.artists_wallet,
+            )?);
+        }
+    }
+    if burn_amount > Uint128::zero() {
+        let exec_cw20_burn = WasmMsg::Execute {
+            contract_addr: cfg.rize_token_address.clone().into(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: burn_amount,
+            })?,
+            funds: vec![],
+        };
+        response = response.add_message(exec_cw20_burn);
+    }
+    if user_amount > Uint128::zero() {
+        if let Some(msg) = send_msg.clone() {
+            response = response.add_message(util::send_token_message(
+                cfg.rize_token_address.clone(),
+                user_amount,
+                recipient.clone(),
+                msg,
+            )?);
+        } else {
+            let exec_cw20_transfer = WasmMsg::Execute {
+                contract_addr: cfg.rize_token_address.clone().into(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.clone().into(),
+                    amount: user_amount,
+                })?,
+                funds: vec![],
+            };
+            response = response.add_message(exec_cw20_transfer);
+        }
+    }
+
+    // a contract receiving the reward through Send cannot take the native bonus with it
+    let mut bonus_amount = Uint128::zero();
+    if let Some((denom, amount)) = bonus {
+        response = response.add_message(util::transfer_native_token_message(
+            Denom::Native(denom),
+            amount,
+            if send_msg.is_some() { owner.clone() } else { recipient.clone() },
+        )?);
+        bonus_amount = amount;
+    }
+
+    // End
+
+    return Ok(response.add_attributes(vec![
+        attr("action", "claim_reward"),
+        attr("address", owner.clone()),
+        attr("recipient", recipient),
+        attr("reward_amount", Uint128::from(reward)),
+        attr("reward_remaining", owed - reward),
+        attr("curation_amount", curation_amount),
+        attr("user_amount", user_amount),
+        attr("charity_amount", charity_amount),
+        attr("burn_amount", burn_amount),
+        attr("artists_amount", artists_amount),
+        attr("bonus_amount", bonus_amount),
+        attr("relayer_fee", fee_amount),
+    ]));
+}
+///////////////////////////////////////////////////////// this func is called when a relayer submits a claim signed by the staker //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_claim_with_permit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: Addr,
+    permit: Permit,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let owner = deps.api.addr_validate(owner.as_str())?;
+    let payload = &permit.payload;
+    if payload.contract != env.contract.address
+        || payload.chain_id != env.block.chain_id
+        || payload.owner != owner
+        || payload.action != (PermitAction::ClaimReward {})
+    {
+        return Err(ContractError::InvalidPermit {});
+    }
+    if payload.expires <= env.block.time.seconds() {
+        return Err(ContractError::PermitExpired {});
+    }
+    let nonce = PERMIT_NONCES.may_load(deps.storage, owner.clone())?.unwrap_or(0);
+    if payload.nonce != nonce {
+        return Err(ContractError::InvalidNonce { expected: nonce });
+    }
+
+    // the key has to belong to the owner and has to have signed this payload
+    let hash = Sha256::digest(to_binary(payload)?.as_slice());
+    let key_address = Ripemd160::digest(Sha256::digest(permit.pubkey.as_slice()));
+    let verified = deps
+        .api
+        .secp256k1_verify(&hash, &permit.signature, &permit.pubkey)
+        .map_err(|_| ContractError::InvalidPermit {})?;
+    if deps.api.addr_canonicalize(owner.as_str())?.as_slice() != &key_address[..] || !verified {
+        return Err(ContractError::InvalidPermit {});
+    }
+    PERMIT_NONCES.save(deps.storage, owner.clone(), &(nonce + 1))?;
+
+    let fee = payload.relayer_fee;
+    let response = claim_reward(
+        deps,
+        env,
+        owner.clone(),
+        ClaimOptions::default(),
+        Some((info.sender.clone(), fee)),
+        owner,
+        None,
+    )?;
+    Ok(response.add_attributes(vec![
+        attr("sender", info.sender),
+        attr("nonce", nonce.to_string()),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for capping the relayer fee of permit claims //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_update_permit_fee_cap(
+    deps: DepsMut,
+    info: MessageInfo,
+    percent: u64,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    if percent > 100 {
+        return Err(ContractError::InvalidInput {});
+    }
+    PERMIT_FEE_CAP.save(deps.storage, &percent)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_permit_fee_cap"),
+        attr("percent", percent.to_string()),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called when the reward of every position is staked again //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_compound(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: Option<Addr>,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+    let mut cfg = CONFIG.load(deps.storage)?;
+    let owner = operator_owner(deps.as_ref(), &env, &info.sender, owner, OperatorPermission::Compound)?;
+
+    // a boost is credited up to now or dropped when the nft was sold
+    let verified = verify_boost(deps.as_ref(), &owner)?;
+    apply_boost(deps.storage, owner.clone(), verified, env.block.time.seconds())?;
+
+    let reward = update_reward(deps.storage, env.clone(), owner.clone())?;
+    if reward == Uint128::zero() {
+        return Err(ContractError::NoReward {});
+    }
+    if cfg.reward_amount < reward {
+        return Err(ContractError::NotEnoughReward {});
+    }
+
+    // each position takes its own reward, curated positions still pay their share to the target
+    let curation_share = CURATION_SHARE.may_load(deps.storage)?.unwrap_or(0);
+    let mut curation_amount = Uint128::zero();
+    let mut response = Response::new();
+    let mut list = STAKERS.load(deps.storage, owner.clone())?;
+    for staker in list.iter_mut() {
+        let mut compounded = staker.reward;
+        if let Some(target) = staker.curation.clone() {
+            let share = staker.reward.multiply_ratio(curation_share, 100u128);
+            if share > Uint128::zero() {
+                let payout = pay_curated_target(deps.storage, &target, share)?;
+                response = response.add_message(util::transfer_token_message(
+                    Denom::Cw20(cfg.rize_token_address.clone()),
+                    share,
+                    payout,
+                )?);
+                curation_amount += share;
+                compounded -= share;
+            }
+            update_curated_stake(deps.storage, &target, compounded, Uint128::zero())?;
+        }
+        // a compounded grant vests with the rest of the grant
+        if let Some(vesting) = &mut staker.vesting {
+            vesting.total += compounded;
+        }
+        staker.amount += compounded;
+        staker.reward = Uint128::zero();
+    }
+    STAKERS.save(deps.storage, owner.clone(), &list)?;
+    update_staker_aggregates(deps.storage, &env, owner.clone())?;
+
+    let compounded = reward - curation_amount;
+    cfg.reward_amount -= reward;
+    cfg.stake_amount += compounded;
+    CONFIG.save(deps.storage, &cfg)?;
+
+    let hooks = prepare_stake_hooks(
+        deps.storage,
+        StakeChangedHookMsg::Compound {
+            addr: owner.clone(),
+            amount: compounded,
+        },
+    )?;
+
+    Ok(response.add_submessages(hooks).add_attributes(vec![
+        attr("action", "compound"),
+        attr("address", owner),
+        attr("sender", info.sender),
+        attr("reward_amount", reward),
+        attr("curation_amount", curation_amount),
+        attr("compounded_amount", compounded),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for finding whose rewards a claim or compound acts on //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+fn operator_owner(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    owner: Option<Addr>,
+    permission: OperatorPermission,
+) -> Result<Addr, ContractError> {
+    let owner = match owner {
+        Some(owner) => deps.api.addr_validate(owner.as_str())?,
+        None => return Ok(sender.clone()),
+    };
+    if owner == *sender {
+        return Ok(owner);
+    }
+
+    match OPERATORS.may_load(deps.storage, (owner.clone(), sender.clone()))? {
+        Some(grant) if !grant.expires.is_expired(&env.block) && grant.permissions.contains(&permission) => Ok(owner),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+///////////////////////////////////////////////////////// this func is called for settling the native bonus of a staker at claim time //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+fn pending_bonus(storage: &dyn Storage, address: Addr) -> Result<Option<(String, BonusInfo)>, ContractError> {
+    let bonus_cfg = match BONUS_CONFIG.may_load(storage)? {
+        Some(bonus_cfg) => bonus_cfg,
+        None => return Ok(None),
+    };
+    let state = BONUS_STATE.load(storage)?;
+
+    // only settled here, the claim stores it once it pays
+    let bonus = settle_bonus(storage, &state, address)?;
+    Ok(Some((bonus_cfg.denom, bonus)))
+}
+
+///////////////////////////////////////////////////////// this func is called for checking the boost collections owned by a staker //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+fn verify_boost(deps: Deps, address: &Addr) -> StdResult<Option<(u64, Addr, String)>> {
+    let collections = BOOST_COLLECTIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Addr, u64)>>>()?;
+
+    // boosts do not stack, the best collection held wins together with the token proving it
+    let mut best: Option<(u64, Addr, String)> = None;
+    for (contract, multiplier) in collections {
+        if best.as_ref().map_or(0, |(best, _, _)| *best) >= multiplier {
+            continue;
+        }
+        let tokens: StdResult<TokensResponse> = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: contract.clone().into(),
+            msg: to_binary(&Cw721QueryMsg::Tokens {
+                owner: address.clone().into(),
+                start_after: None,
+                limit: Some(1),
+            })?,
+        }));
+        // a collection that fails to answer boosts nobody
+        if let Ok(tokens) = tokens {
+            if let Some(token_id) = tokens.tokens.into_iter().next() {
+                best = Some((multiplier, contract, token_id));
+            }
+        }
+    }
+    Ok(best)
+}
+///////////////////////////////////////////////////////// this func is called for storing the verified boost on the positions of a staker //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+fn apply_boost(
+    storage: &mut dyn Storage,
+    address: Addr,
+    verified: Option<(u64, Addr, String)>,
+    now: u64,
+) -> Result<(), ContractError> {
+    let mut list = STAKERS.load(storage, address.clone()).unwrap_or(vec![]);
+    credit_boost(storage, &address, &mut list, &verified, now)?;
+    STAKERS.save(storage, address.clone(), &list)?;
+
+    if let Some((_, collection, token_id)) = verified {
+        BOOST_HOLDERS.save(storage, (collection, token_id), &address)?;
+    }
+    Ok(())
+}
+
+fn credit_boost(
+    storage: &dyn Storage,
+    address: &Addr,
+    list: &mut [StakerInfo],
+    verified: &Option<(u64, Addr, String)>,
+    now: u64,
+) -> StdResult<()> {
+    // the time since the last check only counts when nobody else proved holding the token meanwhile
+    let continuous = match verified {
+        Some((_, collection, token_id)) => {
+            BOOST_HOLDERS.may_load(storage, (collection.clone(), token_id.clone()))?.as_ref() == Some(address)
+        }
+        None => false,
+    };
+
+    for staker in list.iter_mut() {
+        if let (Some(boost), true) = (staker.boost.clone(), continuous) {
+            staker.reward += boost_reward(staker, boost.multiplier, now.saturating_sub(boost.since));
+        }
+        // every check starts a new interval, a failed one drops the boost with what it had not credited
+        staker.boost = verified.as_ref().map(|(multiplier, _, _)| Boost {
+            multiplier: *multiplier,
+            since: now,
+        });
+    }
+    Ok(())
+}
+
+fn curation_key(target: &CurationTarget) -> String {
+    match target {
+        CurationTarget::Artist { address } => format!("artist:{}", address),
+        CurationTarget::Collection { id } => format!("collection:{}", id),
+    }
+}
+
+// the target as it is stored, artists by their normalized address so every spelling shares one key
+fn validate_curation(deps: Deps, target: CurationTarget) -> Result<CurationTarget, ContractError> {
+    match target {
+        CurationTarget::Artist { address } => Ok(CurationTarget::Artist {
+            address: deps.api.addr_validate(address.as_str())?,
+        }),
+        CurationTarget::Collection { id } => {
+            if !COLLECTIONS.has(deps.storage, id.clone()) {
+                return Err(ContractError::UnknownCollection {});
+            }
+            Ok(CurationTarget::Collection { id })
+        }
+    }
+}
+///////////////////////////////////////////////////////// this func is called for updating the ranking of curated targets whenever curated stake changes //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+fn update_curated_stake(
+    storage: &mut dyn Storage,
+    target: &CurationTarget,
+    added: Uint128,
+    removed: Uint128,
+) -> Result<(), ContractError> {
+    let key = curation_key(target);
+    let mut curated = CURATED_TARGETS
+        .may_load(storage, key.clone())?
+        .unwrap_or(CuratedTarget {
+            target: target.clone(),
+            stake: Uint128::zero(),
+            earned: Uint128::zero(),
+        });
+
+    // inverted like the leaderboard so an ascending range walks from the biggest stake
+    if !curated.stake.is_zero() {
+        CURATION_INDEX.remove(storage, (u128::MAX - curated.stake.u128(), key.clone()));
+    }
+    curated.stake = curated.stake + added - removed;
+    if !curated.stake.is_zero() {
+        CURATION_INDEX.save(storage, (u128::MAX - curated.stake.u128(), key.clone()), &curated.stake)?;
+    }
+
+    CURATED_TARGETS.save(storage, key, &curated)?;
+    Ok(())
+}
+///////////////////////////////////////////////////////// this func is called for recording the reward share of a curated target //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+fn credit_claimable(storage: &mut dyn Storage, address: Addr, amount: Uint128) -> StdResult<()> {
+    if amount == Uint128::zero() {
+        return Ok(());
+    }
+    CLAIMABLE_BALANCES.update(storage, address, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default() + amount)
+    })?;
+    Ok(())
+}
+
+// where the share of a curated target is sent, a collection pays its registered address
+fn curation_payout(storage: &dyn Storage, target: &CurationTarget) -> StdResult<Addr> {
+    match target {
+        CurationTarget::Artist { address } => Ok(address.clone()),
+        CurationTarget::Collection { id } => COLLECTIONS.load(storage, id.clone()),
+    }
+}
+
+fn pay_curated_target(storage: &mut dyn Storage, target: &CurationTarget, amount: Uint128) -> Result<Addr, ContractError> {
+    let payout = curation_payout(storage, target)?;
+
+    let key = curation_key(target);
+    let mut curated = CURATED_TARGETS.load(storage, key.clone())?;
+    curated.earned += amount;
+    CURATED_TARGETS.save(storage, key, &curated)?;
+
+    Ok(payout)
+}
+///////////////////////////////////////////////////////// this func is called when we click unstake button on frontend//////////////////////////////////
+///
+///         Using this function, we can unstake all staked token
+///         input params: none
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    index: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+    let (cfg, burned) = release_stake(deps.storage, &env, info.sender.clone(), index as usize, amount)?;
+
+    let exec_cw20_transfer = WasmMsg::Execute {
+        contract_addr: cfg.rize_token_address.clone().into(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.clone().into(),
+            amount: Uint128::from(amount),
+        })?,
+        funds: vec![],
+    };
+
+    let hooks = prepare_stake_hooks(
+        deps.storage,
+        StakeChangedHookMsg::Unstake {
+            addr: info.sender.clone(),
+            amount,
+        },
+    )?;
+
+    return Ok(Response::new()
+        .add_message(exec_cw20_transfer)
+        .add_submessages(hooks)
+        .add_events(burned.map(|id| burn_event(&info.sender, id)))
+        .add_attributes(vec![
+            attr("action", "unstake"),
+            attr("address", info.sender.clone()),
+            attr("unstake_amount", Uint128::from(amount)),
+        ]));
+}
+
+///////////////////////////////////////////////////////// this func is called when user unstakes straight into another contract //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_unstake_and_send(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    amount: Uint128,
+    contract: Addr,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+    let contract = deps.api.addr_validate(contract.as_str())?;
+
+    let list = STAKERS.load(deps.storage, info.sender.clone())?;
+    let i = find_position(&list, id)?;
+    let (cfg, burned) = release_stake(deps.storage, &env, info.sender.clone(), i, amount)?;
+
+    let hooks = prepare_stake_hooks(
+        deps.storage,
+        StakeChangedHookMsg::Unstake {
+            addr: info.sender.clone(),
+            amount,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_message(util::send_token_message(cfg.rize_token_address, amount, contract.clone(), msg)?)
+        .add_submessages(hooks)
+        .add_events(burned.map(|id| burn_event(&info.sender, id)))
+        .add_attributes(vec![
+                attr("action", "unstake_and_send"),
+                attr("address", info.sender),
+                attr("id", id.to_string()),
+                attr("contract", contract),
+                attr("unstake_amount", amount),
+        ]))
+}
+///////////////////////////////////////////////////////// this func is called when user unstakes every unlocked position at once //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_unstake_all(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    claim_rewards: bool,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+    let cfg = CONFIG.load(deps.storage)?;
+
+    let list = STAKERS.load(deps.storage, info.sender.clone()).unwrap_or(vec![]);
+    let unlocked: Vec<(u64, Uint128)> = list
+        .iter()
+        .map(|staker| (staker.id, releasable_amount(staker, env.block.time.seconds())))
+        .filter(|(_, amount)| *amount > Uint128::zero())
+        .collect();
+    if unlocked.is_empty() {
+        return Err(ContractError::StillLocked {});
+    }
+
+    // ids stay put while positions are removed, so each one is looked up again
+    let batch = unlocked.len().min(constants::MAX_UNSTAKE_ALL_POSITIONS);
+    let mut total = Uint128::zero();
+    let mut burns = vec![];
+    for (id, amount) in unlocked.iter().take(batch) {
+        let list = STAKERS.load(deps.storage, info.sender.clone())?;
+        let i = find_position(&list, *id)?;
+        let (_, burned) = release_stake(deps.storage, &env, info.sender.clone(), i, *amount)?;
+        burns.extend(burned.map(|id| burn_event(&info.sender, id)));
+        total += *amount;
+    }
+
+    let exec_cw20_transfer = WasmMsg::Execute {
+        contract_addr: cfg.rize_token_address.into(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.clone().into(),
+            amount: total,
+        })?,
+        funds: vec![],
+    };
+
+    let hooks = prepare_stake_hooks(
+        deps.storage,
+        StakeChangedHookMsg::Unstake {
+            addr: info.sender.clone(),
+            amount: total,
+        },
+    )?;
+
+    let mut response = Response::new()
+        .add_message(exec_cw20_transfer)
+        .add_submessages(hooks)
+        .add_events(burns)
+        .add_attributes(vec![
+            attr("action", "unstake_all"),
+            attr("address", info.sender.clone()),
+            attr("unstake_amount", total),
+            attr("positions", batch.to_string()),
+            attr("remaining", (unlocked.len() - batch).to_string()),
+        ]);
+
+    // a claim locks matured positions again, so it waits for the last batch
+    if claim_rewards && unlocked.len() > batch {
+        response = response.add_attributes(vec![
+            attr("claim_skipped", "true"),
+            attr("claim_skip_reason", "remaining positions"),
+        ]);
+    } else if claim_rewards {
+        // a refused claim writes nothing and does not undo the unstake, the reward of the unstaked positions stays in the claimable balance
+        match claim_reward(
+            deps.branch(),
+            env,
+            info.sender.clone(),
+            ClaimOptions::default(),
+            None,
+            info.sender,
+            None,
+        ) {
+            Ok(claim) => {
+                response = response
+                    .add_submessages(claim.messages)
+                    .add_event(Event::new("claim_reward").add_attributes(claim.attributes))
+                    .add_attribute("claim_skipped", "false");
+            }
+            Err(err) => {
+                response = response.add_attributes(vec![
+                    attr("claim_skipped", "true"),
+                    attr("claim_skip_reason", err.to_string()),
+                ]);
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+// what unstake would let go of right now, nothing while the position is locked
+fn releasable_amount(staker: &StakerInfo, now: u64) -> Uint128 {
+    if staker.unstake_not_before.is_some_and(|time| now < time) {
+        return Uint128::zero();
+    }
+    match &staker.vesting {
+        Some(vesting) => vested_amount(vesting, now).saturating_sub(vesting.released).min(staker.amount),
+        None if now - staker.last_time < staker.lock_type => Uint128::zero(),
+        None => staker.amount,
+    }
+}
+
+///////////////////////////////////////////////////////// this func is called for taking unlocked principal out of a position //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn release_stake(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: Addr,
+    i: usize,
+    amount: Uint128,
+) -> Result<(Config, Option<u64>), ContractError> {
+    let mut cfg = CONFIG.load(storage)?;
+
+    let mut list = STAKERS.load(storage, owner.clone())?;
+    if list.len() <= i {
+        return Err(ContractError::NoStaked {});
+    }
+    
+    if amount == Uint128::zero() {
+        return Err(ContractError::InvalidInput {});
+    }
+    if list[i].amount < amount {
+        return Err(ContractError::NotEnoughStake {});
+    }
+    if cfg.stake_amount < amount {
+        return Err(ContractError::NotEnoughStake {});
+    }
+    if let Some(vesting) = &mut list[i].vesting {
+        // vesting grants skip the tier lock, only the vested part can leave
+        let vested = vested_amount(vesting, env.block.time.seconds());
+        if vested < vesting.released + amount {
+            return Err(ContractError::NotVested {});
+        }
+        vesting.released += amount;
+    } else if env.block.time.seconds() - list[i].last_time < list[i].lock_type {
+        return Err(ContractError::StillLocked {});
+    }
+    // set by the funder of a position staked on behalf of someone
+    if list[i].unstake_not_before.is_some_and(|time| env.block.time.seconds() < time) {
+        return Err(ContractError::StillLocked {});
+    }
+
+    cfg.stake_amount -= amount;
+    CONFIG.save(storage, &cfg)?;
+
+    if let Some(target) = &list[i].curation {
+        update_curated_stake(storage, target, Uint128::zero(), amount)?;
+    }
+
+    // the reward so far is settled at the old amount, the part that leaves moves to the claimable balance
+    settle_position(&mut list[i], env.block.time.seconds());
+    let mut reward = list[i].reward.multiply_ratio(amount, list[i].amount);
+    list[i].reward -= reward;
+    // only a vesting grant leaves before its lock, its principal takes what it earned so far along
+    let locked = list[i].locked_reward.multiply_ratio(amount, list[i].amount);
+    list[i].locked_reward -= locked;
+    reward += locked;
+    if let Some(target) = &list[i].curation {
+        let curation_share = CURATION_SHARE.may_load(storage)?.unwrap_or(0);
+        let share = reward.multiply_ratio(curation_share, 100u128);
+        if share > Uint128::zero() {
+            let payout = pay_curated_target(storage, target, share)?;
+            credit_claimable(storage, payout, share)?;
+            reward -= share;
+        }
+    }
+    credit_claimable(storage, owner.clone(), reward)?;
+
+    // an emptied position is gone together with its nft
+    list[i].amount -= amount;
+    let mut burned = None;
+    if list[i].amount == Uint128::zero() {
+        remove_position_records(storage, list[i].id)?;
+        burned = Some(list.remove(i).id);
+    }
+
+    STAKERS.save(storage, owner.clone(), &list)?;
+    update_staker_aggregates(storage, env, owner)?;
+    Ok((cfg, burned))
+}
+
+///////////////////////////////////////////////////////// this func is called when user offers a position to another wallet //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_transfer_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let recipient = deps.api.addr_validate(recipient.as_str())?;
+    let owner = POSITION_OWNERS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::NoStaked {})?;
+    check_can_transfer(deps.storage, &env, &info.sender, &owner, id)?;
+    if owner == recipient {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    // nobody gets positions pushed onto it, the recipient takes the position with AcceptPosition
+    check_position_limit(&STAKERS.may_load(deps.storage, recipient.clone())?.unwrap_or_default())?;
+    PENDING_TRANSFERS.save(deps.storage, id, &recipient)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "offer_position"),
+        attr("id", id.to_string()),
+        attr("from", owner),
+        attr("to", recipient),
+        attr("sender", info.sender),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called when user takes a position offered to it //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_accept_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    if PENDING_TRANSFERS.may_load(deps.storage, id)?.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    move_position(deps, &env, info.sender.clone(), id, info.sender)
+}
+///////////////////////////////////////////////////////// this func is called when user turns down a position offered to it //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_cancel_position_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let owner = POSITION_OWNERS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::NoStaked {})?;
+    // once offered only the recipient can turn it down, the owner cannot take it back behind a buyer's back
+    if PENDING_TRANSFERS.may_load(deps.storage, id)?.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PENDING_TRANSFERS.remove(deps.storage, id);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_position_transfer"),
+        attr("id", id.to_string()),
+        attr("owner", owner),
+        attr("sender", info.sender),
+    ]))
+}
+
+// moves a position right away, the recipient accepted it or is a contract taking it in its receive handler
+fn move_position(deps: DepsMut, env: &Env, sender: Addr, id: u64, recipient: Addr) -> Result<Response, ContractError> {
+    let (owner, position) = transfer_position(deps.storage, env, id, recipient.clone())?;
+
+    let hooks = prepare_stake_hooks(
+        deps.storage,
+        StakeChangedHookMsg::Transfer {
+            from: owner.clone(),
+            to: recipient.clone(),
+            amount: position.amount,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_event(transfer_event(&sender, &recipient, id))
+        .add_attributes(vec![
+            attr("action", "transfer_position"),
+            attr("id", id.to_string()),
+            attr("from", owner),
+            attr("to", recipient),
+            attr("sender", sender),
+            attr("amount", position.amount),
+        ]))
+}
+///////////////////////////////////////////////////////// this func is called when user allows another wallet to transfer a position //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_approve_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    spender: Addr,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let owner = POSITION_OWNERS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::NoStaked {})?;
+    check_owner_or_operator(deps.storage, &env, &info.sender, &owner)?;
+
+    let spender = deps.api.addr_validate(spender.as_str())?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidInput {});
+    }
+    POSITION_APPROVALS.save(deps.storage, (id, spender.clone()), &expires)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "approve_position"),
+        attr("id", id.to_string()),
+        attr("owner", owner),
+        attr("spender", spender),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called when user withdraws the transfer approval of a position //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_revoke_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    spender: Addr,
+) -> Result<Response, ContractError> {
+    let owner = POSITION_OWNERS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::NoStaked {})?;
+    check_owner_or_operator(deps.storage, &env, &info.sender, &owner)?;
+
+    POSITION_APPROVALS.remove(deps.storage, (id, spender.clone()));
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "revoke_position"),
+        attr("id", id.to_string()),
+        attr("owner", owner),
+        attr("spender", spender),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called when user cuts a position into two with the same terms //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_split_position(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let mut list = STAKERS.load(deps.storage, info.sender.clone())?;
+    let i = find_position(&list, id)?;
+    if amount == Uint128::zero() || amount >= list[i].amount || list[i].vesting.is_some() {
+        return Err(ContractError::InvalidInput {});
+    }
+    check_position_limit(&list)?;
+
+    // the new position keeps the lock, tier, curation and boost, pending reward is shared by amount
+    let mut split = list[i].clone();
+    split.id = next_position_id(deps.storage)?;
+    split.amount = amount;
+    split.reward = list[i].reward.multiply_ratio(amount, list[i].amount);
+    split.locked_reward = list[i].locked_reward.multiply_ratio(amount, list[i].amount);
+    list[i].amount -= amount;
+    list[i].reward -= split.reward;
+    list[i].locked_reward -= split.locked_reward;
+    mint_position_nft(deps.storage, split.id, &info.sender)?;
+
+    let new_id = split.id;
+    list.push(split);
+    // weights and curated stake do not change, so the aggregates stay as they are
+    STAKERS.save(deps.storage, info.sender.clone(), &list)?;
+
+    Ok(Response::new()
+        .add_event(mint_event(&info.sender, &info.sender, new_id))
+        .add_attributes(vec![
+            attr("action", "split_position"),
+            attr("address", info.sender),
+            attr("id", id.to_string()),
+            attr("new_id", new_id.to_string()),
+            attr("amount", amount),
+        ]))
+}
+///////////////////////////////////////////////////////// this func is called when user combines positions of the same tier into one //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_merge_positions(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let mut unique = ids.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    if ids.len() < 2 || unique.len() != ids.len() {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    let mut list = STAKERS.load(deps.storage, info.sender.clone())?;
+    let mut merged = list[find_position(&list, ids[0])?].clone();
+    let mode = MERGE_UNLOCK.may_load(deps.storage)?.unwrap_or_default();
+    let now = env.block.time.seconds();
+    let mut burns = vec![];
+    for id in &ids[1..] {
+        let mut other = list.remove(find_position(&list, *id)?);
+        // each position settles what it earned so far before the unlock times combine, unmatured reward stays locked
+        settle_position(&mut merged, now);
+        settle_position(&mut other, now);
+        // a funder's principal cannot join another position and leave before the funder allowed
+        if other.lock_type != merged.lock_type
+            || other.apy != merged.apy
+            || other.curation != merged.curation
+            || other.funder != merged.funder
+            || other.vesting.is_some()
+            || merged.vesting.is_some()
+        {
+            return Err(ContractError::PositionsMismatch {});
+        }
+        merged.unstake_not_before = merged.unstake_not_before.max(other.unstake_not_before);
+
+        // the tier is the same, so combining the start times combines the unlock times
+        merged.last_time = match mode {
+            MergeUnlock::WeightedAverage => weighted_time(
+                merged.amount,
+                running_lock_start(&merged, now),
+                other.amount,
+                running_lock_start(&other, now),
+            ),
+            MergeUnlock::Latest => merged.last_time.max(other.last_time),
+        };
+        merged.amount += other.amount;
+        merged.reward += other.reward;
+        merged.locked_reward += other.locked_reward;
+        remove_position_records(deps.storage, other.id)?;
+        burns.push(burn_event(&info.sender, other.id));
+    }
+
+    let i = find_position(&list, merged.id)?;
+    list[i] = merged.clone();
+    STAKERS.save(deps.storage, info.sender.clone(), &list)?;
+    update_staker_aggregates(deps.storage, &env, info.sender.clone())?;
+
+    Ok(Response::new().add_events(burns).add_attributes(vec![
+        attr("action", "merge_positions"),
+        attr("address", info.sender),
+        attr("id", merged.id.to_string()),
+        attr("merged", ids[1..].iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",")),
+        attr("amount", merged.amount),
+        attr("unlock_time", (merged.last_time + merged.lock_type).to_string()),
+    ]))
+}
+
+// a matured lock counts as starting one lock before now, so averaging with it never shortens another lock
+fn running_lock_start(staker: &StakerInfo, now: u64) -> u64 {
+    staker.last_time.max(now.saturating_sub(staker.lock_type))
+}
+
+fn weighted_time(amount1: Uint128, time1: u64, amount2: Uint128, time2: u64) -> u64 {
+    let total = amount1 + amount2;
+    if time1 <= time2 {
+        time1 + Uint128::from(time2 - time1).multiply_ratio(amount2, total).u128() as u64
+    } else {
+        time2 + Uint128::from(time1 - time2).multiply_ratio(amount1, total).u128() as u64
+    }
+}
+///////////////////////////////////////////////////////// this func is called when user moves a position to a longer tier //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_extend_lock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    new_tier: u64,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let mut list = STAKERS.load(deps.storage, info.sender.clone())?;
+    let i = find_position(&list, id)?;
+    let new_lock = lock_seconds(new_tier);
+    if new_lock <= list[i].lock_type {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    // reward up to now is kept at the old apy, the longer lock starts from now
+    let old_lock = list[i].lock_type;
+    settle_position(&mut list[i], env.block.time.seconds());
+    list[i].last_time = env.block.time.seconds();
+    list[i].lock_type = new_lock;
+    list[i].apy = None;
+    STAKERS.save(deps.storage, info.sender.clone(), &list)?;
+    update_staker_aggregates(deps.storage, &env, info.sender.clone())?;
+
+    let hooks = prepare_stake_hooks(
+        deps.storage,
+        StakeChangedHookMsg::ExtendLock {
+            addr: info.sender.clone(),
+            amount: list[i].amount,
+            lock_type: new_lock,
+        },
+    )?;
+
+    Ok(Response::new().add_submessages(hooks).add_attributes(vec![
+        attr("action", "extend_lock"),
+        attr("address", info.sender),
+        attr("id", id.to_string()),
+        attr("old_lock", old_lock.to_string()),
+        attr("new_lock", new_lock.to_string()),
+        attr("settled_reward", list[i].reward + list[i].locked_reward),
+        attr("unlock_time", (list[i].last_time + new_lock).to_string()),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called when user lets a bot claim or compound for it //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_grant_operator(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: Addr,
+    permissions: Vec<OperatorPermission>,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let operator = deps.api.addr_validate(operator.as_str())?;
+    let expires = expires.unwrap_or_default();
+    if operator == info.sender || permissions.is_empty() || expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    let names: Vec<String> = permissions.iter().map(|permission| format!("{:?}", permission)).collect();
+    OPERATORS.save(
+        deps.storage,
+        (info.sender.clone(), operator.clone()),
+        &OperatorGrant { permissions, expires },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "grant_operator"),
+        attr("owner", info.sender),
+        attr("operator", operator),
+        attr("permissions", names.join(",")),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called when user removes a claim or compound bot //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_revoke_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: Addr,
+) -> Result<Response, ContractError> {
+    OPERATORS.remove(deps.storage, (info.sender.clone(), operator.clone()));
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "revoke_operator"),
+        attr("owner", info.sender),
+        attr("operator", operator),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for allowing a wallet to fund vesting grants //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_update_vesting_funder(
+    deps: DepsMut,
+    info: MessageInfo,
+    funder: Addr,
+    authorized: bool,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    let funder = deps.api.addr_validate(funder.as_str())?;
+    if authorized {
+        VESTING_FUNDERS.save(deps.storage, funder.clone(), &true)?;
+    } else {
+        VESTING_FUNDERS.remove(deps.storage, funder.clone());
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_vesting_funder"),
+        attr("funder", funder),
+        attr("authorized", authorized.to_string()),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for allowing a wallet to stake on behalf of others //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_update_stake_funder(
+    deps: DepsMut,
+    info: MessageInfo,
+    funder: Addr,
+    authorized: bool,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    let funder = deps.api.addr_validate(funder.as_str())?;
+    if authorized {
+        STAKE_FUNDERS.save(deps.storage, funder.clone(), &true)?;
+    } else {
+        STAKE_FUNDERS.remove(deps.storage, funder.clone());
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_stake_funder"),
+        attr("funder", funder),
+        attr("authorized", authorized.to_string()),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for setting how a top up moves the unlock time of a tier //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_update_top_up_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    tier: u64,
+    policy: TopUpPolicy,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    TOP_UP_POLICIES.save(deps.storage, lock_seconds(tier), &policy)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_top_up_policy"),
+        attr("tier", tier.to_string()),
+        attr("policy", format!("{:?}", policy)),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for setting the apy curve of custom lock durations //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_update_apy_curve(
+    deps: DepsMut,
+    info: MessageInfo,
+    points: Vec<ApyPoint>,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    // positions already staked keep the apy they were quoted
+    if points.is_empty()
+        || points[0].lock_seconds == 0
+        || points.windows(2).any(|pair| pair[0].lock_seconds >= pair[1].lock_seconds)
+    {
+        return Err(ContractError::InvalidInput {});
+    }
+    APY_CURVE.save(deps.storage, &points)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_apy_curve"),
+        attr("min_lock_seconds", points[0].lock_seconds.to_string()),
+        attr("max_lock_seconds", points[points.len() - 1].lock_seconds.to_string()),
+        attr("points", points.len().to_string()),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for setting how merged positions combine their unlock times //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_update_merge_unlock(
+    deps: DepsMut,
+    info: MessageInfo,
+    mode: MergeUnlock,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    MERGE_UNLOCK.save(deps.storage, &mode)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_merge_unlock"),
+        attr("mode", format!("{:?}", mode)),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for instantiating the stRIZE receipt token //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_create_liquid_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    code_id: u64,
+    name: String,
+    symbol: String,
+    apy: Option<u64>,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+    if LIQUID_POOL.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    let cfg = CONFIG.load(deps.storage)?;
+    let rize_info: TokenInfoResponse = deps
+        .querier
+        .query_wasm_smart(cfg.rize_token_address, &Cw20QueryMsg::TokenInfo {})?;
+
+    let apy = apy.unwrap_or(constants::DAYS_30_APY);
+    LIQUID_POOL.save(
+        deps.storage,
+        &LiquidPool {
+            supply: Uint128::zero(),
+            principal: Uint128::zero(),
+            rate: Decimal::one(),
+            apy,
+            last_time: env.block.time.seconds(),
+        },
+    )?;
+
+    let instantiate = WasmMsg::Instantiate {
+        admin: Some(env.contract.address.to_string()),
+        code_id,
+        msg: to_binary(&Cw20InstantiateMsg {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            decimals: rize_info.decimals,
+            initial_balances: vec![],
+            mint: Some(MinterResponse {
+                minter: env.contract.address.into(),
+                cap: None,
+            }),
+            marketing: None,
+        })?,
+        funds: vec![],
+        label: name,
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(instantiate, LIQUID_TOKEN_REPLY_ID))
+        .add_attributes(vec![
+            attr("action", "create_liquid_token"),
+            attr("code_id", code_id.to_string()),
+            attr("symbol", symbol),
+            attr("apy", apy.to_string()),
+        ]))
+}
+///////////////////////////////////////////////////////// this func is called when user transfers a position nft to another wallet //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_transfer_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let id = parse_token_id(&token_id)?;
+    let owner = POSITION_OWNERS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::NoStaked {})?;
+    check_can_transfer(deps.storage, &env, &info.sender, &owner, id)?;
+
+    // like any cw721 the nft changes hands right away, offers are made with TransferPosition
+    move_position(deps, &env, info.sender, id, recipient)
+}
+///////////////////////////////////////////////////////// this func is called when user sends a position nft to a contract //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_send_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let contract = deps.api.addr_validate(&contract)?;
+    let id = parse_token_id(&token_id)?;
+    let owner = POSITION_OWNERS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::NoStaked {})?;
+    check_can_transfer(deps.storage, &env, &info.sender, &owner, id)?;
+
+    // the contract consents by handling the receive message, a failing receive undoes the move
+    let sender = info.sender.clone();
+    let response = move_position(deps, &env, info.sender, id, contract.clone())?;
+
+    let receive: CosmosMsg = Cw721ReceiveMsg {
+        sender: sender.into(),
+        token_id,
+        msg,
+    }
+    .into_cosmos_msg(contract)?;
+
+    Ok(response.add_message(receive))
+}
+///////////////////////////////////////////////////////// this func is called when user allows an operator to move all of its position nfts //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_approve_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let operator = deps.api.addr_validate(&operator)?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidInput {});
+    }
+    NFT_OPERATORS.save(deps.storage, (info.sender.clone(), operator.clone()), &expires)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "approve_all"),
+        attr("owner", info.sender),
+        attr("operator", operator),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called when user removes an operator of its position nfts //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_revoke_all(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    NFT_OPERATORS.remove(deps.storage, (info.sender.clone(), operator.clone()));
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "revoke_all"),
+        attr("owner", info.sender),
+        attr("operator", operator),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for checking ownership//////////////////////////////////
+///
+///         Owner is set when contract is instantiated.
+///         Using this function, we can authorize the ownership
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn check_owner(deps: &DepsMut, info: &MessageInfo) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(Response::new().add_attribute("action", "check_owner"))
+}
+///////////////////////////////////////////////////////// this func is called for checking enable state//////////////////////////////////
+///
+///         Enable state is set when contract is instantiated.
+///         The default vale is true.
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub fn check_enabled(deps: &DepsMut, _info: &MessageInfo) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    if !cfg.enabled {
+        return Err(ContractError::Disabled {});
+    }
+    Ok(Response::new().add_attribute("action", "check_enabled"))
+}
+///////////////////////////////////////////////////////// this func is called for updating the ownership//////////////////////////////////
+///
 ///         Owner is set when contract is instantiated.
 ///         if changing ownership is needed, we can use this function.
 ///         input params: new owner(new walletaddress)
@@ -403,61 +2912,435 @@ pub fn check_enabled(deps: &DepsMut, _info: &MessageInfo) -> Result<Response, Co
 pub fn execute_update_owner(
     deps: DepsMut,
     info: MessageInfo,
-    owner: Addr,
+    owner: Addr,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    CONFIG.update(deps.storage, |mut exists| -> StdResult<_> {
+        exists.owner = owner;
+        Ok(exists)
+    })?;
+    Ok(Response::new().add_attribute("action", "update_owner"))
+}
+///////////////////////////////////////////////////////// this func is called for updating the enable state //////////////////////////////////
+///
+///         If we need changing the enable state of the contract, this function is used.
+///         input params: new state(BOOL)
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub fn execute_update_enabled(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    CONFIG.update(deps.storage, |mut exists| -> StdResult<_> {
+        exists.enabled = enabled;
+        Ok(exists)
+    })?;
+    Ok(Response::new().add_attribute("action", "update_enabled"))
+}
+///////////////////////////////////////////////////////// this func is called for updating reward interval //////////////////////////////////
+///
+///         If we need changing reward interval, this function is used.
+///         input params: new reward_interval(u64)
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub fn execute_update_constants(
+    deps: DepsMut,
+    info: MessageInfo,
+    rize_token_address: Addr,
+    reward_interval: u64,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    CONFIG.update(deps.storage, |mut exists| -> StdResult<_> {
+        exists.rize_token_address = rize_token_address;
+        exists.reward_interval = reward_interval;
+        Ok(exists)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "update_constants"))
+}
+///////////////////////////////////////////////////////// this func is called for updating the native bonus denom and rank threshold //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_update_bonus_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    rank_threshold: u8,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    if denom.is_empty() || rank_threshold > constants::LEADERBOARD_SIZE {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    let state = BONUS_STATE.may_load(deps.storage)?.unwrap_or_default();
+    if let Some(bonus_cfg) = BONUS_CONFIG.may_load(deps.storage)? {
+        // pending bonuses are owed in the current denom
+        if bonus_cfg.denom != denom && (!state.index.is_zero() || !state.undistributed.is_zero()) {
+            return Err(ContractError::InvalidInput {});
+        }
+    }
+
+    BONUS_CONFIG.save(deps.storage, &BonusConfig { denom, rank_threshold })?;
+    BONUS_STATE.save(deps.storage, &state)?;
+
+    // the threshold may have moved across any staker of the leaderboard
+    let addresses = RANK_STAKERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, (addr, _))| addr))
+        .collect::<StdResult<Vec<Addr>>>()?;
+    sync_bonus_eligibility(deps.storage, addresses)?;
+
+    Ok(Response::new().add_attribute("action", "update_bonus_config"))
+}
+///////////////////////////////////////////////////////// this func is called for depositing the native bonus //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_deposit_native_bonus(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // shared by weighted stake between the stakers at or above the rank threshold
+    check_enabled(&deps, &info)?;
+    let bonus_cfg = BONUS_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::BonusNotConfigured {})?;
+    let amount = must_pay(&info, &bonus_cfg.denom)?;
+
+    let mut state = BONUS_STATE.load(deps.storage)?;
+    if state.eligible_weight.is_zero() {
+        state.undistributed += amount;
+    } else {
+        state.index += Decimal::from_ratio(amount + state.undistributed, state.eligible_weight);
+        state.undistributed = Uint128::zero();
+    }
+    BONUS_STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "deposit_native_bonus"),
+        attr("address", info.sender.clone()),
+        attr("denom", bonus_cfg.denom),
+        attr("amount", amount),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for updating the charity and artists wallets for reward //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_update_distribution_wallets(
+    deps: DepsMut,
+    info: MessageInfo,
+    charity_wallet: Addr,
+    artists_wallet: Addr,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    DISTRIBUTION_WALLETS.save(
+        deps.storage,
+        &DistributionWallets {
+            charity_wallet: deps.api.addr_validate(charity_wallet.as_str())?,
+            artists_wallet: deps.api.addr_validate(artists_wallet.as_str())?,
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("action", "update_distribution_wallets"))
+}
+///////////////////////////////////////////////////////// this func is called for updating the reward percent of curated positions paid to the curated target //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_update_curation_share(
+    deps: DepsMut,
+    info: MessageInfo,
+    share: u64,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    if share > 100 {
+        return Err(ContractError::InvalidInput {});
+    }
+    CURATION_SHARE.save(deps.storage, &share)?;
+
+    Ok(Response::new().add_attribute("action", "update_curation_share"))
+}
+///////////////////////////////////////////////////////// this func is called for registering a marketplace collection and its payout wallet //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_register_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: String,
+    payout: Addr,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    if id.is_empty() {
+        return Err(ContractError::InvalidInput {});
+    }
+    let payout = deps.api.addr_validate(payout.as_str())?;
+    COLLECTIONS.save(deps.storage, id.clone(), &payout)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_collection"),
+        attr("collection", id),
+        attr("payout", payout),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for pointing a position at an artist or a collection //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_set_curation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    curation: Option<CurationTarget>,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let mut list = STAKERS.may_load(deps.storage, info.sender.clone())?.unwrap_or_default();
+    let i = find_position(&list, id)?;
+
+    let curation = curation.map(|target| validate_curation(deps.as_ref(), target)).transpose()?;
+    // matured reward belongs to the old target, so it is settled and moved to the claimable balances
+    // reward of a lock still running stays on the position and is paid with the new curation on maturity
+    if list[i].curation != curation {
+        settle_position(&mut list[i], env.block.time.seconds());
+        let mut reward = list[i].reward;
+        list[i].reward = Uint128::zero();
+        if let Some(target) = &list[i].curation {
+            let curation_share = CURATION_SHARE.may_load(deps.storage)?.unwrap_or(0);
+            let share = reward.multiply_ratio(curation_share, 100u128);
+            if share > Uint128::zero() {
+                let payout = pay_curated_target(deps.storage, target, share)?;
+                credit_claimable(deps.storage, payout, share)?;
+                reward -= share;
+            }
+        }
+        credit_claimable(deps.storage, info.sender.clone(), reward)?;
+    }
+
+    if let Some(target) = &list[i].curation {
+        update_curated_stake(deps.storage, target, Uint128::zero(), list[i].amount)?;
+    }
+    if let Some(target) = &curation {
+        update_curated_stake(deps.storage, target, list[i].amount, Uint128::zero())?;
+    }
+
+    list[i].curation = curation.clone();
+    STAKERS.save(deps.storage, info.sender.clone(), &list)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_curation"),
+        attr("address", info.sender.clone()),
+        attr("id", id.to_string()),
+        attr("curation", curation.as_ref().map_or("none".to_string(), curation_key)),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for registering a cw721 collection whose holders get boosted reward //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_register_boost_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: Addr,
+    multiplier: u64,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    // multiplier is based on MULTIPLE, 15000 boosts the reward by half
+    if (multiplier as u128) < MULTIPLE {
+        return Err(ContractError::InvalidInput {});
+    }
+    let contract = deps.api.addr_validate(contract.as_str())?;
+    if !BOOST_COLLECTIONS.has(deps.storage, contract.clone())
+        && BOOST_COLLECTIONS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count()
+            >= constants::MAX_BOOST_COLLECTIONS as usize
+    {
+        return Err(ContractError::TooManyBoostCollections {});
+    }
+    BOOST_COLLECTIONS.save(deps.storage, contract.clone(), &multiplier)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_boost_collection"),
+        attr("contract", contract),
+        attr("multiplier", multiplier.to_string()),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for removing a boost collection //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_remove_boost_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: Addr,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    BOOST_COLLECTIONS.remove(deps.storage, contract.clone());
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_boost_collection"),
+        attr("contract", contract),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called when user refreshes the nft boost of the positions //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_refresh_boost(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    check_enabled(&deps, &info)?;
+
+    let verified = verify_boost(deps.as_ref(), &info.sender)?;
+    let multiplier = verified.as_ref().map_or(0, |(multiplier, _, _)| *multiplier);
+    apply_boost(deps.storage, info.sender.clone(), verified, env.block.time.seconds())?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "refresh_boost"),
+        attr("address", info.sender.clone()),
+        attr("multiplier", multiplier.to_string()),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for weighting the voting power by lock duration or not //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_update_voting_weighted(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    weighted: bool,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    VOTING_WEIGHTED.save(deps.storage, &weighted, env.block.height)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_voting_weighted"),
+        attr("weighted", weighted.to_string()),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for registering a contract notified on every stake change //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: Addr,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    let addr = deps.api.addr_validate(addr.as_str())?;
+    HOOKS.add_hook(deps.storage, addr.clone())?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_hook"),
+        attr("hook", addr),
+    ]))
+}
+///////////////////////////////////////////////////////// this func is called for removing a hook contract //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: Addr,
 ) -> Result<Response, ContractError> {
     // authorize owner
     check_owner(&deps, &info)?;
 
-    CONFIG.update(deps.storage, |mut exists| -> StdResult<_> {
-        exists.owner = owner;
-        Ok(exists)
-    })?;
-    Ok(Response::new().add_attribute("action", "update_owner"))
+    HOOKS.remove_hook(deps.storage, addr.clone())?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_hook"),
+        attr("hook", addr),
+    ]))
 }
-///////////////////////////////////////////////////////// this func is called for updating the enable state //////////////////////////////////
+///////////////////////////////////////////////////////// this func is called for choosing whether a failing hook reverts the operation //////////////////////////////////
 ///
-///         If we need changing the enable state of the contract, this function is used.
-///         input params: new state(BOOL)
+///         
 ///     
 /// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
-
-pub fn execute_update_enabled(
+pub fn execute_update_hooks_ignore_errors(
     deps: DepsMut,
     info: MessageInfo,
-    enabled: bool,
+    ignore_errors: bool,
 ) -> Result<Response, ContractError> {
     // authorize owner
     check_owner(&deps, &info)?;
 
-    CONFIG.update(deps.storage, |mut exists| -> StdResult<_> {
-        exists.enabled = enabled;
-        Ok(exists)
-    })?;
-    Ok(Response::new().add_attribute("action", "update_enabled"))
+    HOOKS_IGNORE_ERRORS.save(deps.storage, &ignore_errors)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_hooks_ignore_errors"),
+        attr("ignore_errors", ignore_errors.to_string()),
+    ]))
 }
-///////////////////////////////////////////////////////// this func is called for updating reward interval //////////////////////////////////
+///////////////////////////////////////////////////////// this func is called for updating the membership levels used by the marketplace //////////////////////////////////
 ///
-///         If we need changing reward interval, this function is used.
-///         input params: new reward_interval(u64)
+///         
 ///     
 /// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
-
-pub fn execute_update_constants(
+pub fn execute_update_membership_levels(
     deps: DepsMut,
     info: MessageInfo,
-    rize_token_address: Addr,
-    reward_interval: u64,
+    levels: Vec<MembershipLevel>,
 ) -> Result<Response, ContractError> {
     // authorize owner
     check_owner(&deps, &info)?;
 
-    CONFIG.update(deps.storage, |mut exists| -> StdResult<_> {
-        exists.rize_token_address = rize_token_address;
-        exists.reward_interval = reward_interval;
-        Ok(exists)
-    })?;
+    for (i, level) in levels.iter().enumerate() {
+        if level.name.is_empty() || levels[..i].iter().any(|other| other.name == level.name) {
+            return Err(ContractError::InvalidInput {});
+        }
+    }
+    MEMBERSHIP_LEVELS.save(deps.storage, &levels)?;
 
-    Ok(Response::new().add_attribute("action", "update_constants"))
+    Ok(Response::new().add_attribute("action", "update_membership_levels"))
 }
 ///////////////////////////////////////////////////////// this func is called for withdrawing reward //////////////////////////////////
 ///
@@ -541,11 +3424,102 @@ pub fn execute_withdraw_stake(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::Staker { address } => to_binary(&query_staker(deps, address)?),
-        QueryMsg::ListStakers { start_after } => to_binary(&query_list_stakers(deps, start_after)?)
+        QueryMsg::ListStakers { start_after } => to_binary(&query_list_stakers(deps, start_after)?),
+        QueryMsg::Leaderboard { limit } => to_binary(&query_leaderboard(deps, limit)?),
+        QueryMsg::Rank { address } => to_binary(&query_rank(deps, address)?),
+        QueryMsg::BonusConfig {} => to_binary(&query_bonus_config(deps)?),
+        QueryMsg::Bonus { address } => to_binary(&query_bonus(deps, address)?),
+        QueryMsg::DistributionWallets {} => to_binary(&query_distribution_wallets(deps)?),
+        QueryMsg::TopCurated { limit } => to_binary(&query_top_curated(deps, limit)?),
+        QueryMsg::CuratedTarget { target } => to_binary(&query_curated_target(deps, target)?),
+        QueryMsg::BoostCollections {} => to_binary(&query_boost_collections(deps)?),
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            to_binary(&query_voting_power_at_height(deps, env, address, height)?)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => {
+            to_binary(&query_total_power_at_height(deps, env, height)?)
+        }
+        QueryMsg::Info {} => to_binary(&query_info(deps)?),
+        QueryMsg::VePower { address, time } => to_binary(&query_ve_power(deps, env, address, time)?),
+        QueryMsg::TotalVePower { time } => to_binary(&query_total_ve_power(deps, env, time)?),
+        QueryMsg::Hooks {} => to_binary(&HOOKS.query_hooks(deps)?),
+        QueryMsg::MembershipLevels {} => to_binary(&query_membership_levels(deps)?),
+        QueryMsg::MembershipLevel { address } => to_binary(&query_membership_level(deps, env, address)?),
+        QueryMsg::MembersAtLevel { level, start_after, limit } => {
+            to_binary(&query_members_at_level(deps, env, level, start_after, limit)?)
+        }
+        QueryMsg::Position { id } => to_binary(&query_position(deps, id)?),
+        QueryMsg::PositionApprovals { id } => to_binary(&query_position_approvals(deps, env, id)?),
+        QueryMsg::PendingTransfer { id } => to_binary(&PendingTransferResponse {
+            id,
+            recipient: PENDING_TRANSFERS.may_load(deps.storage, id)?,
+        }),
+        QueryMsg::LiquidPool {} => to_binary(&query_liquid_pool(deps, env)?),
+        QueryMsg::TopUpPolicies {} => to_binary(&query_top_up_policies(deps)?),
+        QueryMsg::ApyCurve {} => to_binary(&ApyCurveResponse {
+            points: apy_curve(deps.storage)?,
+        }),
+        QueryMsg::QuoteApy { lock_seconds } => to_binary(&QuoteApyResponse {
+            lock_seconds,
+            apy: quote_apy(&apy_curve(deps.storage)?, lock_seconds)
+                .map_err(|err| StdError::generic_err(err.to_string()))?,
+        }),
+        QueryMsg::Vesting { id } => to_binary(&query_vesting(deps, env, id)?),
+        QueryMsg::Operators { owner } => to_binary(&query_operators(deps, owner)?),
+        QueryMsg::PermitNonce { owner } => to_binary(&PermitNonceResponse {
+            nonce: PERMIT_NONCES.may_load(deps.storage, owner)?.unwrap_or(0),
+        }),
+        QueryMsg::PermitFeeCap {} => to_binary(&PermitFeeCapResponse {
+            percent: PERMIT_FEE_CAP.may_load(deps.storage)?.unwrap_or(0),
+        }),
+        QueryMsg::ClaimableBalance { address } => to_binary(&ClaimableBalanceResponse {
+            amount: CLAIMABLE_BALANCES.may_load(deps.storage, address)?.unwrap_or_default(),
+        }),
+        QueryMsg::MergeUnlock {} => to_binary(&MergeUnlockResponse {
+            mode: MERGE_UNLOCK.may_load(deps.storage)?.unwrap_or_default(),
+        }),
+        QueryMsg::OwnerOf { token_id, include_expired } => {
+            to_binary(&query_owner_of(deps, env, parse_token_id(&token_id)?, include_expired.unwrap_or(false))?)
+        }
+        QueryMsg::Approval { token_id, spender, include_expired } => to_binary(&query_nft_approval(
+            deps,
+            env,
+            parse_token_id(&token_id)?,
+            spender,
+            include_expired.unwrap_or(false),
+        )?),
+        QueryMsg::Approvals { token_id, include_expired } => to_binary(&ApprovalsResponse {
+            approvals: nft_approvals(deps, &env, parse_token_id(&token_id)?, include_expired.unwrap_or(false))?,
+        }),
+        QueryMsg::AllOperators { owner, include_expired, start_after, limit } => to_binary(&query_all_operators(
+            deps,
+            env,
+            owner,
+            include_expired.unwrap_or(false),
+            start_after,
+            limit,
+        )?),
+        QueryMsg::NumTokens {} => to_binary(&NumTokensResponse {
+            count: NUM_TOKENS.may_load(deps.storage)?.unwrap_or(0),
+        }),
+        QueryMsg::ContractInfo {} => to_binary(&ContractInfoResponse {
+            name: constants::POSITION_NFT_NAME.to_string(),
+            symbol: constants::POSITION_NFT_SYMBOL.to_string(),
+        }),
+        QueryMsg::NftInfo { token_id } => to_binary(&query_nft_info(deps, parse_token_id(&token_id)?)?),
+        QueryMsg::AllNftInfo { token_id, include_expired } => {
+            let id = parse_token_id(&token_id)?;
+            to_binary(&AllNftInfoResponse {
+                access: query_owner_of(deps, env, id, include_expired.unwrap_or(false))?,
+                info: query_nft_info(deps, id)?,
+            })
+        }
+        QueryMsg::Tokens { owner, start_after, limit } => to_binary(&query_tokens(deps, owner, start_after, limit)?),
+        QueryMsg::AllTokens { start_after, limit } => to_binary(&query_all_tokens(deps, start_after, limit)?),
     }
 }
 ///////////////////////////////////////////////////////// this func is called for getting the state of the contract  //////////////////////////////////
@@ -567,6 +3541,18 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         enabled: cfg.enabled,
     })
 }
+///////////////////////////////////////////////////////// this func is called for getting the charity and artists wallets for reward  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn query_distribution_wallets(deps: Deps) -> StdResult<DistributionWalletsResponse> {
+    let wallets = DISTRIBUTION_WALLETS.load(deps.storage)?;
+    Ok(DistributionWalletsResponse {
+        charity_wallet: wallets.charity_wallet,
+        artists_wallet: wallets.artists_wallet,
+    })
+}
 ///////////////////////////////////////////////////////// this func is called for getting the hole token amout  //////////////////////////////////
 ///
 ///         
@@ -621,9 +3607,604 @@ fn query_list_stakers(deps: Deps, start_after: Option<String>) -> StdResult<Stak
 
     Ok(StakerListResponse { stakers: stakers? })
 }
-///////////////////////////////////////////////////////// this func is called for migration of the contract  //////////////////////////////////
+///////////////////////////////////////////////////////// this func is called for getting the top stakers ordered by weighted stake  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_leaderboard(deps: Deps, limit: Option<u8>) -> StdResult<LeaderboardResponse> {
+    let limit = limit.unwrap_or(constants::LEADERBOARD_SIZE) as usize;
+
+    let ranks: StdResult<Vec<_>> = RANK_STAKERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(rank, (address, weight))| RankResponse {
+                address,
+                weight,
+                rank: Some(rank),
+            })
+        })
+        .collect();
+
+    Ok(LeaderboardResponse { ranks: ranks? })
+}
+///////////////////////////////////////////////////////// this func is called for getting the weighted stake and the rank of a staker  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_rank(deps: Deps, address: Addr) -> StdResult<RankResponse> {
+    // the rank stays empty when the staker is not on the leaderboard
+    let weight = RANKS
+        .may_load(deps.storage, address.clone())?
+        .unwrap_or_default();
+
+    let mut rank = None;
+    for item in RANK_STAKERS.range(deps.storage, None, None, Order::Ascending) {
+        let (position, (addr, _)) = item?;
+        if addr == address {
+            rank = Some(position);
+            break;
+        }
+    }
+
+    Ok(RankResponse { address, weight, rank })
+}
+///////////////////////////////////////////////////////// this func is called for getting the native bonus settings and pool  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_bonus_config(deps: Deps) -> StdResult<BonusConfigResponse> {
+    let bonus_cfg = BONUS_CONFIG.load(deps.storage)?;
+    let state = BONUS_STATE.load(deps.storage)?;
+    Ok(BonusConfigResponse {
+        denom: bonus_cfg.denom,
+        rank_threshold: bonus_cfg.rank_threshold,
+        eligible_weight: state.eligible_weight,
+        undistributed: state.undistributed,
+    })
+}
+///////////////////////////////////////////////////////// this func is called for getting the claimable native bonus of a staker  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_bonus(deps: Deps, address: Addr) -> StdResult<BonusResponse> {
+    let bonus_cfg = BONUS_CONFIG.load(deps.storage)?;
+    let state = BONUS_STATE.load(deps.storage)?;
+    let bonus = settle_bonus(deps.storage, &state, address.clone())?;
+    Ok(BonusResponse {
+        address,
+        denom: bonus_cfg.denom,
+        pending: bonus.pending,
+    })
+}
+///////////////////////////////////////////////////////// this func is called for getting the curated targets ordered by curated stake  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_top_curated(deps: Deps, limit: Option<u32>) -> StdResult<CuratedListResponse> {
+    let limit = limit.unwrap_or(constants::LEADERBOARD_SIZE as u32) as usize;
+
+    let targets: StdResult<Vec<_>> = CURATION_INDEX
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, key) = item?;
+            let curated = CURATED_TARGETS.load(deps.storage, key)?;
+            Ok(CuratedTargetResponse {
+                target: curated.target,
+                stake: curated.stake,
+                earned: curated.earned,
+            })
+        })
+        .collect();
+
+    Ok(CuratedListResponse { targets: targets? })
+}
+///////////////////////////////////////////////////////// this func is called for getting the curated stake and the earnings of a target  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_curated_target(deps: Deps, target: CurationTarget) -> StdResult<CuratedTargetResponse> {
+    let curated = CURATED_TARGETS.may_load(deps.storage, curation_key(&target))?;
+    Ok(match curated {
+        Some(curated) => CuratedTargetResponse {
+            target: curated.target,
+            stake: curated.stake,
+            earned: curated.earned,
+        },
+        None => CuratedTargetResponse {
+            target,
+            stake: Uint128::zero(),
+            earned: Uint128::zero(),
+        },
+    })
+}
+///////////////////////////////////////////////////////// this func is called for getting the cw721 collections boosting the reward  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_boost_collections(deps: Deps) -> StdResult<BoostCollectionsResponse> {
+    let collections: StdResult<Vec<_>> = BOOST_COLLECTIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(contract, multiplier)| BoostCollectionResponse { contract, multiplier }))
+        .collect();
+
+    Ok(BoostCollectionsResponse { collections: collections? })
+}
+///////////////////////////////////////////////////////// this func is called by dao dao for getting the voting power of a staker  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<VotingPowerAtHeightResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+
+    let power = if VOTING_WEIGHTED.may_load_at_height(deps.storage, height)?.unwrap_or(false) {
+        WEIGHTED_BALANCES.may_load_at_height(deps.storage, address, height)?
+    } else {
+        STAKED_BALANCES.may_load_at_height(deps.storage, address, height)?
+    };
+
+    Ok(VotingPowerAtHeightResponse {
+        power: power.unwrap_or_default(),
+        height,
+    })
+}
+///////////////////////////////////////////////////////// this func is called by dao dao for getting the total voting power  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_total_power_at_height(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> StdResult<TotalPowerAtHeightResponse> {
+    let height = height.unwrap_or(env.block.height);
+
+    let power = if VOTING_WEIGHTED.may_load_at_height(deps.storage, height)?.unwrap_or(false) {
+        WEIGHTED_TOTAL.may_load_at_height(deps.storage, height)?
+    } else {
+        STAKED_TOTAL.may_load_at_height(deps.storage, height)?
+    };
+
+    Ok(TotalPowerAtHeightResponse {
+        power: power.unwrap_or_default(),
+        height,
+    })
+}
+///////////////////////////////////////////////////////// this func is called by dao dao for getting the contract version  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_info(deps: Deps) -> StdResult<InfoResponse> {
+    let info = get_contract_version(deps.storage)?;
+    Ok(InfoResponse { info })
+}
+///////////////////////////////////////////////////////// this func is called for getting the decaying vote-escrow power of a staker  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_ve_power(deps: Deps, env: Env, address: Addr, time: Option<u64>) -> StdResult<VePowerResponse> {
+    let time = time.unwrap_or(env.block.time.seconds());
+    let point = user_ve_point(deps.storage, &address, time)?;
+    Ok(VePowerResponse {
+        power: point.bias / Uint128::from(constants::DAYS_720_SECONDS),
+        time,
+    })
+}
+///////////////////////////////////////////////////////// this func is called for getting the decaying vote-escrow power of all stakers  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_total_ve_power(deps: Deps, env: Env, time: Option<u64>) -> StdResult<VePowerResponse> {
+    let time = time.unwrap_or(env.block.time.seconds());
+    let point = total_ve_point(deps.storage, time)?;
+    Ok(VePowerResponse {
+        power: point.bias / Uint128::from(constants::DAYS_720_SECONDS),
+        time,
+    })
+}
+// the highest level whose amount is locked for at least its remaining lock
+fn membership_level(levels: &[MembershipLevel], list: &[StakerInfo], now: u64) -> Option<String> {
+    levels
+        .iter()
+        .rev()
+        .find(|level| {
+            let locked = list
+                .iter()
+                .filter(|staker| (staker.last_time + staker.lock_type).saturating_sub(now) >= level.min_lock_seconds)
+                .fold(Uint128::zero(), |total, staker| total + staker.amount);
+            !locked.is_zero() && locked >= level.min_amount
+        })
+        .map(|level| level.name.clone())
+}
+///////////////////////////////////////////////////////// this func is called for getting the membership levels  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_membership_levels(deps: Deps) -> StdResult<MembershipLevelsResponse> {
+    let levels = MEMBERSHIP_LEVELS.may_load(deps.storage)?.unwrap_or_default();
+    Ok(MembershipLevelsResponse { levels })
+}
+///////////////////////////////////////////////////////// this func is called by the marketplace for getting the membership level of a staker  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_membership_level(deps: Deps, env: Env, address: Addr) -> StdResult<MembershipLevelResponse> {
+    let levels = MEMBERSHIP_LEVELS.may_load(deps.storage)?.unwrap_or_default();
+    let list = STAKERS.may_load(deps.storage, address.clone())?.unwrap_or_default();
+    Ok(MembershipLevelResponse {
+        address,
+        level: membership_level(&levels, &list, env.block.time.seconds()),
+    })
+}
+///////////////////////////////////////////////////////// this func is called by the marketplace for listing the stakers holding a membership level  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_members_at_level(
+    deps: Deps,
+    env: Env,
+    level: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MembersAtLevelResponse> {
+    let levels = MEMBERSHIP_LEVELS.may_load(deps.storage)?.unwrap_or_default();
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.map(|addr| Bound::exclusive(addr.clone()));
+
+    // levels move with time, so stakers are filtered on read and one page only looks at MAX_SCAN of them
+    let mut members = vec![];
+    let mut scanned = 0;
+    let mut next_start_after = None;
+    for item in STAKERS.range(deps.storage, start, None, Order::Ascending) {
+        let (address, list) = item?;
+        scanned += 1;
+        if membership_level(&levels, &list, env.block.time.seconds()).as_ref() == Some(&level) {
+            members.push(address.clone());
+        }
+        // a page that stops early tells where the next one starts, even when it found nobody
+        if members.len() >= limit || scanned >= MAX_SCAN {
+            next_start_after = Some(address);
+            break;
+        }
+    }
+
+    Ok(MembersAtLevelResponse { members, next_start_after })
+}
+///////////////////////////////////////////////////////// this func is called for getting a position by id  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_position(deps: Deps, id: u64) -> StdResult<StakerInfo> {
+    let owner = POSITION_OWNERS.load(deps.storage, id)?;
+    let list = STAKERS.load(deps.storage, owner)?;
+    list.into_iter()
+        .find(|staker| staker.id == id)
+        .ok_or_else(|| StdError::not_found("StakerInfo"))
+}
+///////////////////////////////////////////////////////// this func is called for getting the unexpired transfer approvals of a position  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_position_approvals(deps: Deps, env: Env, id: u64) -> StdResult<PositionApprovalsResponse> {
+    let approvals: StdResult<Vec<_>> = POSITION_APPROVALS
+        .prefix(id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, expires)| !expires.is_expired(&env.block)))
+        .map(|item| item.map(|(spender, expires)| PositionApproval { spender, expires }))
+        .collect();
+
+    Ok(PositionApprovalsResponse { approvals: approvals? })
+}
+///////////////////////////////////////////////////////// this func is called for getting the stRIZE pool with its current rate  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_liquid_pool(deps: Deps, env: Env) -> StdResult<LiquidPoolResponse> {
+    let pool = accrue_liquid_pool(LIQUID_POOL.load(deps.storage)?, env.block.time.seconds());
+    Ok(LiquidPoolResponse {
+        token: LIQUID_TOKEN.may_load(deps.storage)?,
+        supply: pool.supply,
+        principal: pool.principal,
+        rate: pool.rate,
+        apy: pool.apy,
+    })
+}
+
+///////////////////////////////////////////////////////// this func is called for getting the bots allowed to act for an owner  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_operators(deps: Deps, owner: Addr) -> StdResult<OperatorGrantsResponse> {
+    let operators: StdResult<Vec<_>> = OPERATORS
+        .prefix(owner)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(operator, grant)| OperatorGrantResponse {
+                operator,
+                permissions: grant.permissions,
+                expires: grant.expires,
+            })
+        })
+        .collect();
+
+    Ok(OperatorGrantsResponse { operators: operators? })
+}
+///////////////////////////////////////////////////////// this func is called for getting the vested and unvested parts of a vesting grant  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_vesting(deps: Deps, env: Env, id: u64) -> StdResult<VestingResponse> {
+    let position = query_position(deps, id)?;
+    let schedule = position
+        .vesting
+        .ok_or_else(|| StdError::not_found("VestingSchedule"))?;
+
+    let vested = vested_amount(&schedule, env.block.time.seconds());
+    Ok(VestingResponse {
+        id,
+        vested,
+        unvested: schedule.total - vested,
+        unstakable: vested - schedule.released,
+        schedule,
+    })
+}
+///////////////////////////////////////////////////////// this func is called for getting the top up policy of every tier  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_top_up_policies(deps: Deps) -> StdResult<TopUpPoliciesResponse> {
+    let policies: StdResult<Vec<_>> = (0..8)
+        .map(|tier| {
+            let lock_seconds = lock_seconds(tier);
+            Ok(TopUpPolicyResponse {
+                tier,
+                lock_seconds,
+                policy: TOP_UP_POLICIES.may_load(deps.storage, lock_seconds)?.unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    Ok(TopUpPoliciesResponse { policies: policies? })
+}
+
+fn nft_approvals(deps: Deps, env: &Env, id: u64, include_expired: bool) -> StdResult<Vec<Approval>> {
+    POSITION_APPROVALS
+        .prefix(id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| include_expired || item.as_ref().map_or(true, |(_, expires)| !expires.is_expired(&env.block)))
+        .map(|item| item.map(|(spender, expires)| Approval { spender: spender.into(), expires }))
+        .collect()
+}
+///////////////////////////////////////////////////////// this func is called for getting the owner of a position nft  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_owner_of(deps: Deps, env: Env, id: u64, include_expired: bool) -> StdResult<OwnerOfResponse> {
+    let owner = POSITION_OWNERS.load(deps.storage, id)?;
+    Ok(OwnerOfResponse {
+        owner: owner.into(),
+        approvals: nft_approvals(deps, &env, id, include_expired)?,
+    })
+}
+///////////////////////////////////////////////////////// this func is called for getting one approval of a position nft  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_nft_approval(
+    deps: Deps,
+    env: Env,
+    id: u64,
+    spender: String,
+    include_expired: bool,
+) -> StdResult<ApprovalResponse> {
+    let spender = deps.api.addr_validate(&spender)?;
+    let owner = POSITION_OWNERS.load(deps.storage, id)?;
+    if owner == spender {
+        return Ok(ApprovalResponse {
+            approval: Approval { spender: spender.into(), expires: Expiration::Never {} },
+        });
+    }
+
+    match POSITION_APPROVALS.may_load(deps.storage, (id, spender.clone()))? {
+        Some(expires) if include_expired || !expires.is_expired(&env.block) => Ok(ApprovalResponse {
+            approval: Approval { spender: spender.into(), expires },
+        }),
+        _ => Err(StdError::not_found("Approval")),
+    }
+}
+///////////////////////////////////////////////////////// this func is called for getting the operators of an owner  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_all_operators(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    include_expired: bool,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OperatorsResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = maybe_addr(deps.api, start_after)?.map(Bound::exclusive);
+
+    let operators: StdResult<Vec<_>> = NFT_OPERATORS
+        .prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| include_expired || item.as_ref().map_or(true, |(_, expires)| !expires.is_expired(&env.block)))
+        .take(limit)
+        .map(|item| item.map(|(operator, expires)| Approval { spender: operator.into(), expires }))
+        .collect();
+
+    Ok(OperatorsResponse { operators: operators? })
+}
+///////////////////////////////////////////////////////// this func is called for getting the metadata of a position nft  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_nft_info(deps: Deps, id: u64) -> StdResult<NftInfoResponse<PositionMetadata>> {
+    let position = query_position(deps, id)?;
+    Ok(NftInfoResponse {
+        token_uri: None,
+        extension: PositionMetadata {
+            amount: position.amount,
+            tier: lock_tier(position.lock_type),
+            lock_seconds: position.lock_type,
+            unlock_time: position.last_time + position.lock_type,
+        },
+    })
+}
+///////////////////////////////////////////////////////// this func is called for getting the position nfts of an owner  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(parse_token_id).transpose()?;
+
+    let mut ids: Vec<u64> = STAKERS
+        .may_load(deps.storage, owner)?
+        .unwrap_or_default()
+        .iter()
+        .map(|staker| staker.id)
+        .filter(|id| start.is_none() || Some(*id) > start)
+        .collect();
+    ids.sort_unstable();
+
+    Ok(TokensResponse {
+        tokens: ids.into_iter().take(limit).map(|id| id.to_string()).collect(),
+    })
+}
+///////////////////////////////////////////////////////// this func is called for getting all position nfts  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+fn query_all_tokens(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(parse_token_id).transpose()?.map(Bound::exclusive);
+
+    let tokens: StdResult<Vec<String>> = POSITION_OWNERS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| id.map(|id| id.to_string()))
+        .collect();
+
+    Ok(TokensResponse { tokens: tokens? })
+}
+///////////////////////////////////////////////////////// this func is called when a submessage replies  //////////////////////////////////
+///
+///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        LIQUID_TOKEN_REPLY_ID => {
+            let res = parse_reply_instantiate_data(msg)?;
+            let token = deps.api.addr_validate(&res.contract_address)?;
+            LIQUID_TOKEN.save(deps.storage, &token)?;
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "liquid_token_created"),
+                attr("token", token),
+            ]))
+        }
+        // only sent when hook errors are ignored, the failed hook is rolled back on its own
+        HOOK_REPLY_ID => Ok(Response::new().add_attributes(vec![
+            attr("action", "hook_failed"),
+            attr("error", msg.result.unwrap_err()),
+        ])),
+        id => Err(ContractError::UnknownReply { id }),
+    }
+}
+///////////////////////////////////////////////////////// this func is called by owner for rebuilding a page of stakers after a migration //////////////////////////////////
 ///
 ///         
+///     
+/// /////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn execute_migrate_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // authorize owner
+    check_owner(&deps, &info)?;
+
+    // stakers who staked before the leaderboard, the voting power history and the ve checkpoints existed
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let addresses = STAKERS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for address in addresses.iter() {
+        // positions staked before ids existed
+        let mut list = STAKERS.load(deps.storage, address.clone())?;
+        for staker in list.iter_mut().filter(|staker| staker.id == 0) {
+            staker.id = next_position_id(deps.storage)?;
+            mint_position_nft(deps.storage, staker.id, address)?;
+        }
+        STAKERS.save(deps.storage, address.clone(), &list)?;
+
+        update_staker_aggregates(deps.storage, &env, address.clone())?;
+    }
+
+    // the next page starts after the last staker rebuilt, a short page is the last one
+    let mut response = Response::new().add_attributes(vec![
+        attr("action", "migrate_batch"),
+        attr("migrated", addresses.len().to_string()),
+    ]);
+    if let Some(last) = addresses.last() {
+        response = response.add_attribute("last", last.clone());
+    }
+    Ok(response)
+}
+///////////////////////////////////////////////////////// this func is called for migration of the contract  //////////////////////////////////
+///
+///         stakers are rebuilt afterwards page by page with MigrateBatch
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
@@ -633,5 +4214,11 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
             previous_contract: version.contract,
         });
     }
-    Ok(Response::default())
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "migrate"),
+        attr("previous_version", version.version),
+        attr("version", CONTRACT_VERSION),
+    ]))
 }
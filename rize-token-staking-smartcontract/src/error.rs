@@ -1,5 +1,6 @@
 use cosmwasm_std::{StdError};
-use cw_utils::{Expiration, Scheduled};
+use cw_controllers::HookError;
+use cw_utils::{Expiration, ParseReplyError, PaymentError, Scheduled};
 use hex::FromHexError;
 use thiserror::Error;
 
@@ -11,6 +12,15 @@ pub enum ContractError {
     #[error("{0}")]
     Hex(#[from] FromHexError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -45,6 +55,51 @@ pub enum ContractError {
     #[error("Not enough Reward")]
     NotEnoughReward { },
 
+    #[error("Bonus not configured")]
+    BonusNotConfigured {},
+
+    #[error("Distribution exceeds 100 percent")]
+    InvalidDistribution {},
+
+    #[error("Distribution wallets not configured")]
+    DistributionNotConfigured {},
+
+    #[error("Unknown collection")]
+    UnknownCollection {},
+
+    #[error("Too many boost collections")]
+    TooManyBoostCollections {},
+
+    #[error("Too many positions")]
+    TooManyPositions {},
+
+    #[error("Amount is below the minimum stake")]
+    BelowMinimumStake {},
+
+    #[error("Unknown reply id {id}")]
+    UnknownReply { id: u64 },
+
+    #[error("Liquid token not configured")]
+    LiquidTokenNotConfigured {},
+
+    #[error("Positions differ in tier, curation, funder or vesting")]
+    PositionsMismatch {},
+
+    #[error("Amount exceeds the vested stake")]
+    NotVested {},
+
+    #[error("Invalid permit")]
+    InvalidPermit {},
+
+    #[error("Lock duration is outside the apy curve")]
+    InvalidLockDuration {},
+
+    #[error("Permit expired")]
+    PermitExpired {},
+
+    #[error("Invalid permit nonce, expected {expected}")]
+    InvalidNonce { expected: u64 },
+
     #[error("Still in Lock period")]
     StillInLock { },
 
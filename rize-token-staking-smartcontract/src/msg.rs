@@ -1,10 +1,12 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
 
+use cw2::ContractVersion;
 use cw20::{Cw20ReceiveMsg, Denom};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub rize_token_address: Addr,
     pub reward_interval: u64,
@@ -12,20 +14,125 @@ pub struct InstantiateMsg {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StakerInfo {
+    #[serde(default)]
+    pub id: u64,
     pub address: Addr,
     pub amount: Uint128,
     pub reward: Uint128,
     pub last_time: u64,
     pub lock_type: u64,
+    pub curation: Option<CurationTarget>,
+    pub boost: Option<Boost>,
+    // who sent the tokens, differs from address when staked on behalf of someone
+    pub funder: Option<Addr>,
+    pub unstake_not_before: Option<u64>,
+    pub vesting: Option<VestingSchedule>,
+    // set for custom lock durations, the apy the curve quoted when staking
+    #[serde(default)]
+    pub apy: Option<u64>,
+    // reward up to this time is already in reward, the lock still runs from last_time
+    #[serde(default)]
+    pub settled_time: Option<u64>,
+    // reward settled before the lock ran out, it moves to reward once the position matures
+    #[serde(default)]
+    pub locked_reward: Uint128,
+}
+
+// nothing vests before start + cliff, then total vests linearly from start until start + duration
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingSchedule {
+    pub total: Uint128,
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub released: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Boost {
+    pub multiplier: u64,
+    pub since: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CurationTarget {
+    Artist { address: Addr },
+    Collection { id: String },
+}
+
+// how the unlock times of merged positions are combined
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeUnlock {
+    WeightedAverage,
+    #[default]
+    Latest,
+}
+
+// signed off chain by the owner, a relayer submits it and pays the gas
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Permit {
+    pub payload: PermitPayload,
+    pub pubkey: Binary,
+    pub signature: Binary,
+}
+
+// the signature covers the sha256 of the json of this payload
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PermitPayload {
+    pub contract: Addr,
+    pub chain_id: String,
+    pub owner: Addr,
+    pub nonce: u64,
+    pub expires: u64,
+    pub action: PermitAction,
+    pub relayer_fee: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PermitAction {
+    ClaimReward {},
+}
+
+// what an operator may do for the owner, rewards always go to the owner
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OperatorPermission {
+    Claim,
+    Compound,
 }
 
-// #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-// pub struct RewardDistribution {
-//     pub juno_reward: bool,
-//     pub charity: u64,
-//     pub burn: u64,
-//     pub artists: u64,
-// }
+// how a top up moves the unlock time of a position
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TopUpPolicy {
+    #[default]
+    Reset,
+    WeightedAverage,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApyPoint {
+    pub lock_seconds: u64,
+    pub apy: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct RewardDistribution {
+    pub charity: u64,
+    pub burn: u64,
+    pub artists: u64,
+}
+
+// how a claim splits the reward, which positions it settles and how much it may pay
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct ClaimOptions {
+    pub distribution: RewardDistribution,
+    pub ids: Option<Vec<u64>>,
+    pub max_amount: Option<Uint128>,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -36,15 +143,102 @@ pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
     WithdrawReward { amount: Uint128 },
     WithdrawStake { amount: Uint128 },
-    ClaimReward { },
+    ClaimReward {
+        distribution: Option<RewardDistribution>,
+        owner: Option<Addr>,
+        recipient: Option<Addr>,
+        ids: Option<Vec<u64>>,
+        max_amount: Option<Uint128>,
+    },
+    ClaimAndSend { contract: Addr, msg: Binary },
+    Compound { owner: Option<Addr> },
+    ClaimWithPermit { owner: Addr, permit: Permit },
+    UpdatePermitFeeCap { percent: u64 },
     Unstake { index: u64, amount: Uint128 },
+    UnstakeAndSend { id: u64, amount: Uint128, contract: Addr, msg: Binary },
+    UnstakeAll { claim_rewards: bool },
+    UpdateBonusConfig { denom: String, rank_threshold: u8 },
+    DepositNativeBonus { },
+    UpdateDistributionWallets { charity_wallet: Addr, artists_wallet: Addr },
+    UpdateCurationShare { share: u64 },
+    RegisterCollection { id: String, payout: Addr },
+    SetCuration { id: u64, curation: Option<CurationTarget> },
+    RegisterBoostCollection { contract: Addr, multiplier: u64 },
+    RemoveBoostCollection { contract: Addr },
+    RefreshBoost {},
+    UpdateVotingWeighted { weighted: bool },
+    AddHook { addr: Addr },
+    RemoveHook { addr: Addr },
+    UpdateHooksIgnoreErrors { ignore_errors: bool },
+    UpdateMembershipLevels { levels: Vec<MembershipLevel> },
+    TransferPosition { id: u64, recipient: Addr },
+    AcceptPosition { id: u64 },
+    CancelPositionTransfer { id: u64 },
+    ApprovePosition { id: u64, spender: Addr, expires: Option<Expiration> },
+    RevokePosition { id: u64, spender: Addr },
+    CreateLiquidToken { code_id: u64, name: String, symbol: String, apy: Option<u64> },
+    SplitPosition { id: u64, amount: Uint128 },
+    MergePositions { ids: Vec<u64> },
+    UpdateMergeUnlock { mode: MergeUnlock },
+    ExtendLock { id: u64, new_tier: u64 },
+    UpdateTopUpPolicy { tier: u64, policy: TopUpPolicy },
+    UpdateApyCurve { points: Vec<ApyPoint> },
+    UpdateVestingFunder { funder: Addr, authorized: bool },
+    UpdateStakeFunder { funder: Addr, authorized: bool },
+    GrantOperator { operator: Addr, permissions: Vec<OperatorPermission>, expires: Option<Expiration> },
+    RevokeOperator { operator: Addr },
+    MigrateBatch { start_after: Option<Addr>, limit: Option<u32> },
+    // cw721 interface, the token id is the position id
+    TransferNft { recipient: String, token_id: String },
+    SendNft { contract: String, token_id: String, msg: Binary },
+    Approve { spender: String, token_id: String, expires: Option<Expiration> },
+    Revoke { spender: String, token_id: String },
+    ApproveAll { operator: String, expires: Option<Expiration> },
+    RevokeAll { operator: String },
+}
+
+// levels go from the lowest to the highest, a staker holds the highest level it qualifies for
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MembershipLevel {
+    pub name: String,
+    pub min_amount: Uint128,
+    pub min_lock_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeChangedHookMsg {
+    Stake { addr: Addr, amount: Uint128, lock_type: u64 },
+    Unstake { addr: Addr, amount: Uint128 },
+    Compound { addr: Addr, amount: Uint128 },
+    Transfer { from: Addr, to: Addr, amount: Uint128 },
+    ExtendLock { addr: Addr, amount: Uint128, lock_type: u64 },
+}
+
+// the message registered hook contracts have to accept
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeChangedExecuteMsg {
+    StakeChangeHook(StakeChangedHookMsg),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ReceiveMsg {
-    Stake { lock_type: u64 },
+    Stake {
+        #[serde(default)]
+        lock_type: u64,
+        // any duration within the apy curve, used instead of lock_type when set
+        lock_seconds: Option<u64>,
+        curation: Option<CurationTarget>,
+        beneficiary: Option<Addr>,
+        unstake_not_before: Option<u64>,
+    },
     DepositReward { },
+    IncreasePosition { id: u64 },
+    GrantVesting { beneficiary: Addr, lock_type: u64, cliff: u64, duration: u64 },
+    LiquidStake {},
+    Redeem {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -53,6 +247,47 @@ pub enum QueryMsg {
     Config {},
     Staker { address: Addr },
     ListStakers { start_after: Option<String> },
+    Leaderboard { limit: Option<u8> },
+    Rank { address: Addr },
+    BonusConfig {},
+    Bonus { address: Addr },
+    DistributionWallets {},
+    TopCurated { limit: Option<u32> },
+    CuratedTarget { target: CurationTarget },
+    BoostCollections {},
+    VotingPowerAtHeight { address: String, height: Option<u64> },
+    TotalPowerAtHeight { height: Option<u64> },
+    Info {},
+    VePower { address: Addr, time: Option<u64> },
+    TotalVePower { time: Option<u64> },
+    Hooks {},
+    MembershipLevels {},
+    MembershipLevel { address: Addr },
+    MembersAtLevel { level: String, start_after: Option<String>, limit: Option<u32> },
+    Position { id: u64 },
+    PositionApprovals { id: u64 },
+    PendingTransfer { id: u64 },
+    LiquidPool {},
+    MergeUnlock {},
+    TopUpPolicies {},
+    ApyCurve {},
+    QuoteApy { lock_seconds: u64 },
+    Vesting { id: u64 },
+    Operators { owner: Addr },
+    PermitNonce { owner: Addr },
+    PermitFeeCap {},
+    ClaimableBalance { address: Addr },
+    // cw721 interface, the token id is the position id
+    OwnerOf { token_id: String, include_expired: Option<bool> },
+    Approval { token_id: String, spender: String, include_expired: Option<bool> },
+    Approvals { token_id: String, include_expired: Option<bool> },
+    AllOperators { owner: String, include_expired: Option<bool>, start_after: Option<String>, limit: Option<u32> },
+    NumTokens {},
+    ContractInfo {},
+    NftInfo { token_id: String },
+    AllNftInfo { token_id: String, include_expired: Option<bool> },
+    Tokens { owner: String, start_after: Option<String>, limit: Option<u32> },
+    AllTokens { start_after: Option<String>, limit: Option<u32> },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -74,6 +309,202 @@ pub struct StakerListResponse {
     pub stakers: Vec<Vec<StakerInfo>>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RankResponse {
+    pub address: Addr,
+    pub weight: Uint128,
+    pub rank: Option<u8>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LeaderboardResponse {
+    pub ranks: Vec<RankResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BonusConfigResponse {
+    pub denom: String,
+    pub rank_threshold: u8,
+    pub eligible_weight: Uint128,
+    pub undistributed: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BonusResponse {
+    pub address: Addr,
+    pub denom: String,
+    pub pending: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DistributionWalletsResponse {
+    pub charity_wallet: Addr,
+    pub artists_wallet: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CuratedTargetResponse {
+    pub target: CurationTarget,
+    pub stake: Uint128,
+    pub earned: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CuratedListResponse {
+    pub targets: Vec<CuratedTargetResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BoostCollectionResponse {
+    pub contract: Addr,
+    pub multiplier: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BoostCollectionsResponse {
+    pub collections: Vec<BoostCollectionResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VotingPowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TotalPowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct InfoResponse {
+    pub info: ContractVersion,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VePowerResponse {
+    pub power: Uint128,
+    pub time: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MembershipLevelsResponse {
+    pub levels: Vec<MembershipLevel>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MembershipLevelResponse {
+    pub address: Addr,
+    pub level: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MembersAtLevelResponse {
+    pub members: Vec<Addr>,
+    // set while stakers are left to look at, the start_after of the next page
+    pub next_start_after: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PositionApproval {
+    pub spender: Addr,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PendingTransferResponse {
+    pub id: u64,
+    pub recipient: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PositionApprovalsResponse {
+    pub approvals: Vec<PositionApproval>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MergeUnlockResponse {
+    pub mode: MergeUnlock,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PermitNonceResponse {
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ClaimableBalanceResponse {
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PermitFeeCapResponse {
+    pub percent: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OperatorGrantResponse {
+    pub operator: Addr,
+    pub permissions: Vec<OperatorPermission>,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OperatorGrantsResponse {
+    pub operators: Vec<OperatorGrantResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VestingResponse {
+    pub id: u64,
+    pub schedule: VestingSchedule,
+    pub vested: Uint128,
+    pub unvested: Uint128,
+    pub unstakable: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TopUpPolicyResponse {
+    pub tier: u64,
+    pub lock_seconds: u64,
+    pub policy: TopUpPolicy,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TopUpPoliciesResponse {
+    pub policies: Vec<TopUpPolicyResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ApyCurveResponse {
+    pub points: Vec<ApyPoint>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct QuoteApyResponse {
+    pub lock_seconds: u64,
+    pub apy: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LiquidPoolResponse {
+    pub token: Option<Addr>,
+    pub supply: Uint128,
+    pub principal: Uint128,
+    pub rate: Decimal,
+    pub apy: u64,
+}
+
+// nft extension rendered by the marketplace, tier is the lock_type index used on stake and none for a custom lock
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PositionMetadata {
+    pub amount: Uint128,
+    pub tier: Option<u64>,
+    pub lock_seconds: u64,
+    pub unlock_time: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Token1ForToken2PriceResponse {
     pub token2_amount: Uint128,
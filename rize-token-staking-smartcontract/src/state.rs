@@ -1,10 +1,14 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw20::Denom;
-use cw_storage_plus::{Item, Map};
-use crate::msg::{ StakerInfo};
+use cw_controllers::Hooks;
+use cw_utils::Expiration;
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
+use crate::msg::{
+    ApyPoint, CurationTarget, MembershipLevel, MergeUnlock, OperatorPermission, StakerInfo, TopUpPolicy,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -26,4 +30,208 @@ pub const RANK_STAKERS_KEY: &str = "rank_stakers";
 pub const RANK_STAKERS: Map<u8, (Addr, Uint128)> = Map::new(RANK_STAKERS_KEY);
 
 pub const RANKS_KEY: &str = "ranks";
-pub const RANKS: Map<Addr, Uint128> = Map::new(RANKS_KEY);
\ No newline at end of file
+pub const RANKS: Map<Addr, Uint128> = Map::new(RANKS_KEY);
+
+pub const RANK_INDEX_KEY: &str = "rank_index";
+pub const RANK_INDEX: Map<(u128, Addr), Uint128> = Map::new(RANK_INDEX_KEY);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BonusConfig {
+    pub denom: String,
+    pub rank_threshold: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct BonusState {
+    pub index: Decimal,
+    pub eligible_weight: Uint128,
+    pub undistributed: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct BonusInfo {
+    pub weight: Uint128,
+    pub index: Decimal,
+    pub pending: Uint128,
+}
+
+pub const BONUS_CONFIG_KEY: &str = "bonus_config";
+pub const BONUS_CONFIG: Item<BonusConfig> = Item::new(BONUS_CONFIG_KEY);
+
+pub const BONUS_STATE_KEY: &str = "bonus_state";
+pub const BONUS_STATE: Item<BonusState> = Item::new(BONUS_STATE_KEY);
+
+pub const BONUS_STAKERS_KEY: &str = "bonus_stakers";
+pub const BONUS_STAKERS: Map<Addr, BonusInfo> = Map::new(BONUS_STAKERS_KEY);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributionWallets {
+    pub charity_wallet: Addr,
+    pub artists_wallet: Addr,
+}
+
+pub const DISTRIBUTION_WALLETS_KEY: &str = "distribution_wallets";
+pub const DISTRIBUTION_WALLETS: Item<DistributionWallets> = Item::new(DISTRIBUTION_WALLETS_KEY);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CuratedTarget {
+    pub target: CurationTarget,
+    pub stake: Uint128,
+    pub earned: Uint128,
+}
+
+pub const CURATION_SHARE_KEY: &str = "curation_share";
+pub const CURATION_SHARE: Item<u64> = Item::new(CURATION_SHARE_KEY);
+
+pub const COLLECTIONS_KEY: &str = "collections";
+pub const COLLECTIONS: Map<String, Addr> = Map::new(COLLECTIONS_KEY);
+
+pub const CURATED_TARGETS_KEY: &str = "curated_targets";
+pub const CURATED_TARGETS: Map<String, CuratedTarget> = Map::new(CURATED_TARGETS_KEY);
+
+pub const CURATION_INDEX_KEY: &str = "curation_index";
+pub const CURATION_INDEX: Map<(u128, String), Uint128> = Map::new(CURATION_INDEX_KEY);
+
+pub const BOOST_COLLECTIONS_KEY: &str = "boost_collections";
+pub const BOOST_COLLECTIONS: Map<Addr, u64> = Map::new(BOOST_COLLECTIONS_KEY);
+
+// the staker that last proved it holds a boost token, keyed by (collection, token id)
+pub const BOOST_HOLDERS_KEY: &str = "boost_holders";
+pub const BOOST_HOLDERS: Map<(Addr, String), Addr> = Map::new(BOOST_HOLDERS_KEY);
+
+pub const STAKED_BALANCES: SnapshotMap<Addr, Uint128> = SnapshotMap::new(
+    "staked_balances",
+    "staked_balances__checkpoints",
+    "staked_balances__changelog",
+    Strategy::EveryBlock,
+);
+
+pub const STAKED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
+    "staked_total",
+    "staked_total__checkpoints",
+    "staked_total__changelog",
+    Strategy::EveryBlock,
+);
+
+pub const WEIGHTED_BALANCES: SnapshotMap<Addr, Uint128> = SnapshotMap::new(
+    "weighted_balances",
+    "weighted_balances__checkpoints",
+    "weighted_balances__changelog",
+    Strategy::EveryBlock,
+);
+
+pub const WEIGHTED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
+    "weighted_total",
+    "weighted_total__checkpoints",
+    "weighted_total__changelog",
+    Strategy::EveryBlock,
+);
+
+// snapshotted so past heights keep the voting power they were governed by
+pub const VOTING_WEIGHTED: SnapshotItem<bool> = SnapshotItem::new(
+    "voting_weighted",
+    "voting_weighted__checkpoints",
+    "voting_weighted__changelog",
+    Strategy::EveryBlock,
+);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct VePoint {
+    pub bias: Uint128,
+    pub slope: Uint128,
+}
+
+pub const VE_POINTS_KEY: &str = "ve_points";
+pub const VE_POINTS: Map<u64, VePoint> = Map::new(VE_POINTS_KEY);
+
+pub const VE_SLOPE_CHANGES_KEY: &str = "ve_slope_changes";
+pub const VE_SLOPE_CHANGES: Map<u64, Uint128> = Map::new(VE_SLOPE_CHANGES_KEY);
+
+pub const USER_VE_POINTS_KEY: &str = "user_ve_points";
+pub const USER_VE_POINTS: Map<(Addr, u64), VePoint> = Map::new(USER_VE_POINTS_KEY);
+
+pub const USER_VE_SLOPE_CHANGES_KEY: &str = "user_ve_slope_changes";
+pub const USER_VE_SLOPE_CHANGES: Map<(Addr, u64), Uint128> = Map::new(USER_VE_SLOPE_CHANGES_KEY);
+
+pub const HOOKS: Hooks = Hooks::new("hooks");
+
+pub const HOOKS_IGNORE_ERRORS_KEY: &str = "hooks_ignore_errors";
+pub const HOOKS_IGNORE_ERRORS: Item<bool> = Item::new(HOOKS_IGNORE_ERRORS_KEY);
+
+pub const MEMBERSHIP_LEVELS_KEY: &str = "membership_levels";
+pub const MEMBERSHIP_LEVELS: Item<Vec<MembershipLevel>> = Item::new(MEMBERSHIP_LEVELS_KEY);
+
+pub const POSITION_COUNT_KEY: &str = "position_count";
+pub const POSITION_COUNT: Item<u64> = Item::new(POSITION_COUNT_KEY);
+
+pub const POSITION_OWNERS_KEY: &str = "position_owners";
+pub const POSITION_OWNERS: Map<u64, Addr> = Map::new(POSITION_OWNERS_KEY);
+
+// the number of position nfts in existence, kept up to date on mint and burn
+pub const NUM_TOKENS_KEY: &str = "num_tokens";
+pub const NUM_TOKENS: Item<u64> = Item::new(NUM_TOKENS_KEY);
+
+// the address a position is offered to, the position only moves once that address accepts it
+pub const PENDING_TRANSFERS_KEY: &str = "pending_transfers";
+pub const PENDING_TRANSFERS: Map<u64, Addr> = Map::new(PENDING_TRANSFERS_KEY);
+
+pub const POSITION_APPROVALS_KEY: &str = "position_approvals";
+pub const POSITION_APPROVALS: Map<(u64, Addr), Expiration> = Map::new(POSITION_APPROVALS_KEY);
+
+pub const NFT_OPERATORS_KEY: &str = "nft_operators";
+pub const NFT_OPERATORS: Map<(Addr, Addr), Expiration> = Map::new(NFT_OPERATORS_KEY);
+
+pub const LIQUID_TOKEN_KEY: &str = "liquid_token";
+pub const LIQUID_TOKEN: Item<Addr> = Item::new(LIQUID_TOKEN_KEY);
+
+// flexible pool backing the stRIZE receipt token, one stRIZE is worth `rate` RIZE
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LiquidPool {
+    pub supply: Uint128,
+    pub principal: Uint128,
+    pub rate: Decimal,
+    pub apy: u64,
+    pub last_time: u64,
+}
+
+pub const LIQUID_POOL_KEY: &str = "liquid_pool";
+pub const LIQUID_POOL: Item<LiquidPool> = Item::new(LIQUID_POOL_KEY);
+
+pub const MERGE_UNLOCK_KEY: &str = "merge_unlock";
+pub const MERGE_UNLOCK: Item<MergeUnlock> = Item::new(MERGE_UNLOCK_KEY);
+
+// keyed by the lock duration in seconds
+pub const TOP_UP_POLICIES_KEY: &str = "top_up_policies";
+pub const TOP_UP_POLICIES: Map<u64, TopUpPolicy> = Map::new(TOP_UP_POLICIES_KEY);
+
+pub const VESTING_FUNDERS_KEY: &str = "vesting_funders";
+pub const VESTING_FUNDERS: Map<Addr, bool> = Map::new(VESTING_FUNDERS_KEY);
+
+// wallets allowed to stake on behalf of someone else
+pub const STAKE_FUNDERS_KEY: &str = "stake_funders";
+pub const STAKE_FUNDERS: Map<Addr, bool> = Map::new(STAKE_FUNDERS_KEY);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OperatorGrant {
+    pub permissions: Vec<OperatorPermission>,
+    pub expires: Expiration,
+}
+
+// keyed by (owner, operator)
+pub const OPERATORS_KEY: &str = "operators";
+pub const OPERATORS: Map<(Addr, Addr), OperatorGrant> = Map::new(OPERATORS_KEY);
+
+pub const PERMIT_NONCES_KEY: &str = "permit_nonces";
+pub const PERMIT_NONCES: Map<Addr, u64> = Map::new(PERMIT_NONCES_KEY);
+
+// most of a claimed reward a relayer can take, in percent
+pub const PERMIT_FEE_CAP_KEY: &str = "permit_fee_cap";
+pub const PERMIT_FEE_CAP: Item<u64> = Item::new(PERMIT_FEE_CAP_KEY);
+
+// reward settled from positions that no longer exist, paid out by the next claim
+pub const CLAIMABLE_BALANCES_KEY: &str = "claimable_balances";
+pub const CLAIMABLE_BALANCES: Map<Addr, Uint128> = Map::new(CLAIMABLE_BALANCES_KEY);
+
+// control points sorted by lock duration, the first and last one bound custom lock durations
+pub const APY_CURVE_KEY: &str = "apy_curve";
+pub const APY_CURVE: Item<Vec<ApyPoint>> = Item::new(APY_CURVE_KEY);
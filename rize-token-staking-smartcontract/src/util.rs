@@ -1,7 +1,7 @@
 use crate::error::ContractError;
 use crate::state::CONFIG;
 use cosmwasm_std::{
-    to_binary, Addr, BalanceResponse as NativeBalanceResponse, BankMsg, BankQuery, Coin, CosmosMsg,
+    to_binary, Addr, BalanceResponse as NativeBalanceResponse, Binary, BankMsg, BankQuery, Coin, CosmosMsg,
     QuerierWrapper, QueryRequest, Response, StdResult, Storage, Uint128, WasmMsg, WasmQuery,
 };
 use cw20::{Balance, BalanceResponse as CW20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Denom};
@@ -68,6 +68,24 @@ pub fn transfer_token_message(
     }
 }
 
+// cw20 Send, the receiving contract gets a Receive hook carrying msg
+pub fn send_token_message(
+    token: Addr,
+    amount: Uint128,
+    contract: Addr,
+    msg: Binary,
+) -> Result<CosmosMsg, ContractError> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.into(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: contract.into(),
+            amount,
+            msg,
+        })?,
+    }))
+}
+
 pub fn get_token_amount(
     querier: QuerierWrapper,
     denom: Denom,
@@ -0,0 +1,131 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::StdResult;
+use cw_multi_test::AppResponse;
+use rize_staking::constants::{DAYS_30_APY, DAYS_30_SECONDS, DAYS_60_APY, DAYS_720_APY, DAYS_720_SECONDS};
+use rize_staking::msg::{ApyCurveResponse, ApyPoint, ExecuteMsg, QueryMsg, QuoteApyResponse, ReceiveMsg};
+use rize_staking::ContractError;
+
+const AMOUNT: u128 = 1_000_000;
+
+fn quote(suite: &Suite, lock_seconds: u64) -> StdResult<u64> {
+    let response: StdResult<QuoteApyResponse> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.staking, &QueryMsg::QuoteApy { lock_seconds });
+    response.map(|response| response.apy)
+}
+
+fn stake_for(suite: &mut Suite, sender: &str, lock_seconds: u64) -> anyhow::Result<AppResponse> {
+    suite.send(
+        sender,
+        AMOUNT,
+        &ReceiveMsg::Stake {
+            lock_type: 0,
+            lock_seconds: Some(lock_seconds),
+            curation: None,
+            beneficiary: None,
+            unstake_not_before: None,
+        },
+    )
+}
+
+fn update_curve(suite: &mut Suite, sender: &str, points: &[(u64, u64)]) -> anyhow::Result<AppResponse> {
+    let points = points
+        .iter()
+        .map(|(lock_seconds, apy)| ApyPoint {
+            lock_seconds: *lock_seconds,
+            apy: *apy,
+        })
+        .collect();
+    suite.execute(sender, ExecuteMsg::UpdateApyCurve { points })
+}
+
+#[test]
+fn the_default_curve_runs_through_the_tiers() {
+    let suite = Suite::new();
+    let curve: ApyCurveResponse = suite.query(QueryMsg::ApyCurve {});
+    assert_eq!(curve.points.len(), 8);
+    assert_eq!(quote(&suite, DAYS_30_SECONDS).unwrap(), DAYS_30_APY);
+    assert_eq!(quote(&suite, DAYS_720_SECONDS).unwrap(), DAYS_720_APY);
+    // halfway between the 30 and the 60 day tier
+    assert_eq!(quote(&suite, 45 * DAY).unwrap(), (DAYS_30_APY + DAYS_60_APY) / 2);
+
+    // nothing outside the curve can be quoted
+    assert!(quote(&suite, DAYS_30_SECONDS - 1).is_err());
+    assert!(quote(&suite, DAYS_720_SECONDS + 1).is_err());
+}
+
+#[test]
+fn the_owner_sets_the_curve_and_stakes_keep_their_quote() {
+    let mut suite = Suite::new();
+    assert_error(update_curve(&mut suite, ALICE, &[(10 * DAY, 500)]), ContractError::Unauthorized {});
+    assert_error(
+        update_curve(&mut suite, OWNER, &[(100 * DAY, 5000), (10 * DAY, 500)]),
+        ContractError::InvalidInput {},
+    );
+    update_curve(&mut suite, OWNER, &[(10 * DAY, 500), (100 * DAY, 5000)]).unwrap();
+    assert_eq!(quote(&suite, 55 * DAY).unwrap(), 2750);
+
+    let response = stake_for(&mut suite, ALICE, 55 * DAY).unwrap();
+    assert_eq!(suite.attr(&response, "apy"), "2750");
+    assert_error(stake_for(&mut suite, ALICE, 101 * DAY), ContractError::InvalidLockDuration {});
+    assert_error(stake_for(&mut suite, ALICE, 9 * DAY), ContractError::InvalidLockDuration {});
+
+    // a later curve does not touch the apy a position was staked with
+    update_curve(&mut suite, OWNER, &[(10 * DAY, 100), (100 * DAY, 200)]).unwrap();
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.apy, Some(2750));
+    assert_eq!(position.lock_type, 55 * DAY);
+}
+
+#[test]
+fn extending_a_custom_lock_settles_at_its_quote_and_moves_to_the_tier() {
+    let mut suite = Suite::new();
+    stake_for(&mut suite, ALICE, 45 * DAY).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+    // a tier shorter than the custom lock is no extension
+    assert_error(
+        suite.execute(ALICE, ExecuteMsg::ExtendLock { id, new_tier: 0 }),
+        ContractError::InvalidInput {},
+    );
+
+    suite.advance(10 * DAY);
+    suite.execute(ALICE, ExecuteMsg::ExtendLock { id, new_tier: 7 }).unwrap();
+    let position = &suite.positions(ALICE)[0];
+    let quoted = (DAYS_30_APY + DAYS_60_APY) / 2;
+    // the lock has not run out, so the settled reward waits for the new one
+    assert_eq!(position.reward.u128(), 0);
+    assert_eq!(position.locked_reward.u128(), settled(AMOUNT, quoted, 10 * DAY));
+    assert_eq!(position.lock_type, DAYS_720_SECONDS);
+    assert_eq!(position.apy, None);
+    assert_eq!(position.last_time, suite.now());
+}
+
+#[test]
+fn custom_locks_merge_only_with_the_same_lock_and_quote() {
+    let mut suite = Suite::new();
+    stake_for(&mut suite, ALICE, 45 * DAY).unwrap();
+    stake_for(&mut suite, ALICE, 45 * DAY).unwrap();
+    stake_for(&mut suite, ALICE, 50 * DAY).unwrap();
+    let ids: Vec<u64> = suite.positions(ALICE).iter().map(|position| position.id).collect();
+
+    assert_error(
+        suite.execute(ALICE, ExecuteMsg::MergePositions { ids: vec![ids[0], ids[2]] }),
+        ContractError::PositionsMismatch {},
+    );
+    suite.execute(ALICE, ExecuteMsg::MergePositions { ids: vec![ids[0], ids[1]] }).unwrap();
+    let merged = &suite.positions(ALICE)[0];
+    assert_eq!(merged.amount.u128(), 2 * AMOUNT);
+    assert_eq!(merged.lock_type, 45 * DAY);
+
+    // the same lock quoted on another curve is another position
+    update_curve(&mut suite, OWNER, &[(10 * DAY, 500), (100 * DAY, 5000)]).unwrap();
+    stake_for(&mut suite, ALICE, 45 * DAY).unwrap();
+    let requoted = suite.positions(ALICE).last().unwrap().id;
+    assert_error(
+        suite.execute(ALICE, ExecuteMsg::MergePositions { ids: vec![ids[0], requoted] }),
+        ContractError::PositionsMismatch {},
+    );
+}
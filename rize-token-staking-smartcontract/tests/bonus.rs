@@ -0,0 +1,79 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coins, Addr};
+use rize_staking::msg::{BonusResponse, ExecuteMsg, QueryMsg};
+
+fn setup_bonus(suite: &mut Suite, rank_threshold: u8) {
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::UpdateBonusConfig {
+                denom: BONUS_DENOM.to_string(),
+                rank_threshold,
+            },
+        )
+        .unwrap();
+}
+
+fn pending(suite: &Suite, address: &str) -> u128 {
+    let response: BonusResponse = suite.query(QueryMsg::Bonus {
+        address: Addr::unchecked(address),
+    });
+    response.pending.u128()
+}
+
+#[test]
+fn only_stakers_within_the_threshold_share_the_bonus() {
+    let mut suite = Suite::new();
+    setup_bonus(&mut suite, 1);
+    suite.stake(ALICE, 1_000, 7).unwrap();
+    suite.stake(BOB, 500, 7).unwrap();
+
+    suite
+        .execute_with_funds(OWNER, ExecuteMsg::DepositNativeBonus {}, &coins(900, BONUS_DENOM))
+        .unwrap();
+    assert_eq!(pending(&suite, ALICE), 900);
+    assert_eq!(pending(&suite, BOB), 0);
+
+    // the bonus is paid by a claim even without any RIZE reward
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.native_balance(ALICE), 900);
+    assert_eq!(pending(&suite, ALICE), 0);
+}
+
+#[test]
+fn eligible_stakers_share_by_weighted_stake() {
+    let mut suite = Suite::new();
+    setup_bonus(&mut suite, 2);
+    suite.stake(ALICE, 1_000, 7).unwrap();
+    suite.stake(BOB, 500, 7).unwrap();
+    suite.stake(CAROL, 100, 7).unwrap();
+
+    suite
+        .execute_with_funds(OWNER, ExecuteMsg::DepositNativeBonus {}, &coins(900, BONUS_DENOM))
+        .unwrap();
+    assert_eq!(pending(&suite, ALICE), 600);
+    assert_eq!(pending(&suite, BOB), 300);
+    assert_eq!(pending(&suite, CAROL), 0);
+}
+
+#[test]
+fn deposits_wait_for_an_eligible_staker() {
+    let mut suite = Suite::new();
+    setup_bonus(&mut suite, 1);
+    suite
+        .execute_with_funds(OWNER, ExecuteMsg::DepositNativeBonus {}, &coins(500, BONUS_DENOM))
+        .unwrap();
+
+    suite.stake(ALICE, 1_000, 7).unwrap();
+    assert_eq!(pending(&suite, ALICE), 500);
+
+    // a staker leaving the leaderboard stops earning
+    suite.stake(BOB, 2_000, 7).unwrap();
+    suite
+        .execute_with_funds(OWNER, ExecuteMsg::DepositNativeBonus {}, &coins(300, BONUS_DENOM))
+        .unwrap();
+    assert_eq!(pending(&suite, ALICE), 500);
+    assert_eq!(pending(&suite, BOB), 300);
+}
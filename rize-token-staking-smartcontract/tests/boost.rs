@@ -0,0 +1,202 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Empty};
+use cw721_base::{Extension, MintMsg};
+use cw_multi_test::Executor;
+use rize_staking::constants::{DAYS_360_APY, DAYS_720_APY, ONE_YEAR_SECONDS};
+use rize_staking::msg::{Boost, ExecuteMsg};
+
+const MULTIPLIER: u64 = 15000;
+const AMOUNT: u128 = 1_000_000;
+
+type Cw721ExecuteMsg = cw721_base::ExecuteMsg<Extension, Empty>;
+
+// a registered boost collection with a single token minted to alice
+fn setup() -> (Suite, Addr) {
+    let mut suite = Suite::new();
+    let code_id = suite.app.store_code(cw721_contract());
+    let nft = suite
+        .app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &cw721_base::InstantiateMsg {
+                name: "Rize Art".to_string(),
+                symbol: "ART".to_string(),
+                minter: OWNER.to_string(),
+            },
+            &[],
+            "nft",
+            None,
+        )
+        .unwrap();
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            nft.clone(),
+            &Cw721ExecuteMsg::Mint(MintMsg {
+                token_id: "1".to_string(),
+                owner: ALICE.to_string(),
+                token_uri: None,
+                extension: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::RegisterBoostCollection {
+                contract: nft.clone(),
+                multiplier: MULTIPLIER,
+            },
+        )
+        .unwrap();
+    (suite, nft)
+}
+
+fn transfer(suite: &mut Suite, nft: &Addr, from: &str, to: &str) {
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(from),
+            nft.clone(),
+            &Cw721ExecuteMsg::TransferNft {
+                recipient: to.to_string(),
+                token_id: "1".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+fn refresh(suite: &mut Suite, sender: &str) -> String {
+    let response = suite.execute(sender, ExecuteMsg::RefreshBoost {}).unwrap();
+    suite.attr(&response, "multiplier")
+}
+
+// what a 720 day position of AMOUNT earns on top of its apy for the given time
+fn boosted(seconds: u64) -> u128 {
+    AMOUNT * (DAYS_720_APY * (MULTIPLIER - 10000)) as u128 * seconds as u128
+        / (10000 * 10000 * ONE_YEAR_SECONDS) as u128
+}
+
+#[test]
+fn verified_intervals_are_credited_and_reanchored() {
+    let (mut suite, _) = setup();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+
+    assert_eq!(refresh(&mut suite, ALICE), MULTIPLIER.to_string());
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.reward.u128(), 0);
+    assert_eq!(
+        position.boost,
+        Some(Boost {
+            multiplier: MULTIPLIER,
+            since: suite.now(),
+        })
+    );
+
+    suite.advance(10 * DAY);
+    refresh(&mut suite, ALICE);
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.reward.u128(), boosted(10 * DAY));
+    assert_eq!(position.boost.as_ref().unwrap().since, suite.now());
+
+    // the next check only credits the time since the last one
+    suite.advance(5 * DAY);
+    refresh(&mut suite, ALICE);
+    assert_eq!(suite.positions(ALICE)[0].reward.u128(), boosted(10 * DAY) + boosted(5 * DAY));
+}
+
+#[test]
+fn the_boost_is_dropped_once_the_nft_moves() {
+    let (mut suite, nft) = setup();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    suite.stake(BOB, AMOUNT, 7).unwrap();
+    refresh(&mut suite, ALICE);
+
+    suite.advance(10 * DAY);
+    transfer(&mut suite, &nft, ALICE, BOB);
+    assert_eq!(refresh(&mut suite, BOB), MULTIPLIER.to_string());
+    // bob starts from its own check, nothing before it
+    assert_eq!(suite.positions(BOB)[0].reward.u128(), 0);
+
+    suite.advance(10 * DAY);
+    assert_eq!(refresh(&mut suite, ALICE), "0");
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.boost, None);
+    assert_eq!(position.reward.u128(), 0);
+
+    refresh(&mut suite, BOB);
+    assert_eq!(suite.positions(BOB)[0].reward.u128(), boosted(10 * DAY));
+}
+
+#[test]
+fn passing_the_nft_back_does_not_credit_the_time_it_was_away() {
+    let (mut suite, nft) = setup();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    suite.stake(BOB, AMOUNT, 7).unwrap();
+    refresh(&mut suite, ALICE);
+
+    suite.advance(10 * DAY);
+    transfer(&mut suite, &nft, ALICE, BOB);
+    refresh(&mut suite, BOB);
+    transfer(&mut suite, &nft, BOB, ALICE);
+
+    suite.advance(10 * DAY);
+    refresh(&mut suite, ALICE);
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.reward.u128(), 0);
+    assert_eq!(position.boost.as_ref().unwrap().since, suite.now());
+
+    suite.advance(10 * DAY);
+    refresh(&mut suite, ALICE);
+    assert_eq!(suite.positions(ALICE)[0].reward.u128(), boosted(10 * DAY));
+}
+
+#[test]
+fn settling_a_position_does_not_credit_unverified_boost() {
+    let (mut suite, _) = setup();
+    suite.stake(ALICE, AMOUNT, 6).unwrap();
+    refresh(&mut suite, ALICE);
+
+    suite.advance(10 * DAY);
+    // extending settles the base reward and starts a new boost interval
+    let id = suite.positions(ALICE)[0].id;
+    suite.execute(ALICE, ExecuteMsg::ExtendLock { id, new_tier: 7 }).unwrap();
+    let locked = suite.positions(ALICE)[0].locked_reward.u128();
+    assert_eq!(locked, settled(AMOUNT, DAYS_360_APY, 10 * DAY));
+
+    suite.advance(DAY);
+    refresh(&mut suite, ALICE);
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.reward.u128(), boosted(DAY));
+    assert_eq!(position.locked_reward.u128(), locked);
+}
+
+#[test]
+fn a_refused_claim_in_unstake_all_leaves_the_boost_alone() {
+    let (mut suite, _) = setup();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    refresh(&mut suite, ALICE);
+    let locked = suite.positions(ALICE)[1].clone();
+
+    // the pool is empty, so the claim after the unstake is refused
+    suite.advance(40 * DAY);
+    let response = suite.execute(ALICE, ExecuteMsg::UnstakeAll { claim_rewards: true }).unwrap();
+    assert_eq!(suite.attr(&response, "claim_skipped"), "true");
+    assert_eq!(suite.attr(&response, "claim_skip_reason"), "Not enough Reward");
+
+    // the boost interval was not credited nor moved by the refused claim
+    let positions = suite.positions(ALICE);
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].reward, locked.reward);
+    assert_eq!(positions[0].boost, locked.boost);
+
+    refresh(&mut suite, ALICE);
+    assert_eq!(suite.positions(ALICE)[0].reward.u128(), boosted(40 * DAY));
+}
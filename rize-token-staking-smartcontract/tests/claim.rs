@@ -0,0 +1,44 @@
+mod common;
+
+use common::*;
+use rize_staking::constants::DAYS_30_APY;
+
+const AMOUNT: u128 = 1_000_000;
+
+#[test]
+fn claiming_selected_positions_leaves_the_others_alone() {
+    let mut suite = Suite::new();
+    suite.deposit_reward(INITIAL_BALANCE).unwrap();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let positions = suite.positions(ALICE);
+    suite.advance(31 * DAY);
+
+    let balance = suite.balance(ALICE);
+    suite.execute(ALICE, claim_msg(Some(vec![positions[0].id]), None)).unwrap();
+    assert_eq!(suite.balance(ALICE) - balance, reward_for(AMOUNT, DAYS_30_APY, 31 * DAY));
+
+    let after = suite.positions(ALICE);
+    assert_eq!(after[0].last_time, suite.now());
+    // the unselected position is neither settled nor locked again
+    assert_eq!(after[1], positions[1]);
+
+    suite.advance(DAY);
+    let balance = suite.balance(ALICE);
+    suite.execute(ALICE, claim_msg(Some(vec![positions[1].id]), None)).unwrap();
+    assert_eq!(suite.balance(ALICE) - balance, reward_for(AMOUNT, DAYS_30_APY, 32 * DAY));
+}
+
+#[test]
+fn claiming_everything_settles_every_position() {
+    let mut suite = Suite::new();
+    suite.deposit_reward(INITIAL_BALANCE).unwrap();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    suite.advance(31 * DAY);
+
+    let balance = suite.balance(ALICE);
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE) - balance, 2 * reward_for(AMOUNT, DAYS_30_APY, 31 * DAY));
+    assert!(suite.positions(ALICE).iter().all(|position| position.last_time == suite.now()));
+}
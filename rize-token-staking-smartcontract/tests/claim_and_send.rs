@@ -0,0 +1,87 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{to_binary, Addr, Binary, Uint128};
+use rize_staking::constants::DAYS_30_APY;
+use rize_staking::msg::ExecuteMsg;
+
+const AMOUNT: u128 = 1_000_000;
+
+// alice with a matured position and a funded pool
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite.deposit_reward(AMOUNT).unwrap();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    suite.advance(31 * DAY);
+    suite
+}
+
+fn reward() -> u128 {
+    reward_for(AMOUNT, DAYS_30_APY, 31 * DAY)
+}
+
+#[test]
+fn a_claim_pays_the_chosen_recipient() {
+    let mut suite = setup();
+    let response = suite
+        .execute(
+            ALICE,
+            ExecuteMsg::ClaimReward {
+                distribution: None,
+                owner: None,
+                recipient: Some(Addr::unchecked(BOB)),
+                ids: None,
+                max_amount: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(suite.attr(&response, "recipient"), BOB);
+    assert_eq!(suite.balance(BOB), INITIAL_BALANCE + reward());
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE - AMOUNT);
+}
+
+#[test]
+fn claim_and_send_delivers_the_reward_with_the_message() {
+    let mut suite = setup();
+    let receiver = suite.instantiate_receiver();
+    let msg = to_binary(&"list").unwrap();
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::ClaimAndSend {
+                contract: receiver.clone(),
+                msg: msg.clone(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(suite.balance(receiver.as_str()), reward());
+    let received = suite.received(&receiver);
+    assert_eq!(received.len(), 1);
+    // cw20 reports the staking contract as the sender of the tokens
+    assert_eq!(received[0].sender, suite.staking.to_string());
+    assert_eq!(received[0].amount, Uint128::new(reward()));
+    assert_eq!(received[0].msg, msg);
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE - AMOUNT);
+}
+
+#[test]
+fn a_refusing_contract_undoes_the_claim() {
+    let mut suite = setup();
+    let receiver = suite.instantiate_receiver();
+    assert!(suite
+        .execute(
+            ALICE,
+            ExecuteMsg::ClaimAndSend {
+                contract: receiver.clone(),
+                msg: Binary::from(b"fail".to_vec()),
+            },
+        )
+        .is_err());
+    assert_eq!(suite.balance(receiver.as_str()), 0);
+
+    // the reward is still there for a plain claim
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE - AMOUNT + reward());
+}
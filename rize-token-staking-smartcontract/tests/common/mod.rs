@@ -0,0 +1,303 @@
+#![allow(dead_code)]
+
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{
+    coins, to_binary, Addr, Binary, Coin, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+    Uint128,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use rize_staking::constants::ONE_YEAR_SECONDS;
+use rize_staking::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, StakerInfo};
+use rize_staking::ContractError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+pub const OWNER: &str = "owner";
+pub const ALICE: &str = "alice";
+pub const BOB: &str = "bob";
+pub const CAROL: &str = "carol";
+pub const FUNDER: &str = "funder";
+
+pub const BONUS_DENOM: &str = "ujuno";
+
+pub const DAY: u64 = 86400;
+pub const INITIAL_BALANCE: u128 = 1_000_000_000;
+
+pub fn staking_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        rize_staking::contract::execute,
+        rize_staking::contract::instantiate,
+        rize_staking::contract::query,
+    )
+    .with_reply(rize_staking::contract::reply)
+    .with_migrate(rize_staking::contract::migrate);
+    Box::new(contract)
+}
+
+pub fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn cw721_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    );
+    Box::new(contract)
+}
+
+// a contract taking RIZE through cw20 Send, it keeps what it was sent and refuses the message "fail"
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiverMsg {
+    Receive(Cw20ReceiveMsg),
+}
+
+const RECEIVED: Item<Vec<Cw20ReceiveMsg>> = Item::new("received");
+
+fn receiver_instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    RECEIVED.save(deps.storage, &vec![])?;
+    Ok(Response::new())
+}
+
+fn receiver_execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: ReceiverMsg) -> StdResult<Response> {
+    let ReceiverMsg::Receive(received) = msg;
+    if received.msg == Binary::from(b"fail".to_vec()) {
+        return Err(StdError::generic_err("refused"));
+    }
+    RECEIVED.update(deps.storage, |mut all| -> StdResult<_> {
+        all.push(received);
+        Ok(all)
+    })?;
+    Ok(Response::new())
+}
+
+fn receiver_query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_binary(&RECEIVED.load(deps.storage)?)
+}
+
+pub fn receiver_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(receiver_execute, receiver_instantiate, receiver_query))
+}
+
+// the RIZE token, the staking contract and funded test wallets
+pub struct Suite {
+    pub app: App,
+    pub token: Addr,
+    pub staking: Addr,
+    pub staking_code_id: u64,
+    pub cw20_code_id: u64,
+}
+
+impl Suite {
+    pub fn new() -> Self {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked(OWNER), coins(INITIAL_BALANCE, BONUS_DENOM))
+                .unwrap();
+        });
+        let cw20_code_id = app.store_code(cw20_contract());
+        let staking_code_id = app.store_code(staking_contract());
+
+        let initial_balances = [OWNER, ALICE, BOB, CAROL, FUNDER]
+            .iter()
+            .map(|address| Cw20Coin {
+                address: address.to_string(),
+                amount: Uint128::new(INITIAL_BALANCE),
+            })
+            .collect();
+        let token = app
+            .instantiate_contract(
+                cw20_code_id,
+                Addr::unchecked(OWNER),
+                &cw20_base::msg::InstantiateMsg {
+                    name: "Rize".to_string(),
+                    symbol: "RIZE".to_string(),
+                    decimals: 6,
+                    initial_balances,
+                    mint: None,
+                    marketing: None,
+                },
+                &[],
+                "rize",
+                None,
+            )
+            .unwrap();
+
+        let staking = app
+            .instantiate_contract(
+                staking_code_id,
+                Addr::unchecked(OWNER),
+                &InstantiateMsg {
+                    rize_token_address: token.clone(),
+                    reward_interval: DAY,
+                },
+                &[],
+                "rize-staking",
+                Some(OWNER.to_string()),
+            )
+            .unwrap();
+
+        Suite {
+            app,
+            token,
+            staking,
+            staking_code_id,
+            cw20_code_id,
+        }
+    }
+
+    pub fn now(&self) -> u64 {
+        self.app.block_info().time.seconds()
+    }
+
+    pub fn height(&self) -> u64 {
+        self.app.block_info().height
+    }
+
+    // blocks are five seconds apart
+    pub fn advance(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += (seconds / 5).max(1);
+        });
+    }
+
+    pub fn send(&mut self, sender: &str, amount: u128, msg: &ReceiveMsg) -> AnyResult<AppResponse> {
+        let token = self.token.clone();
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            token,
+            &Cw20ExecuteMsg::Send {
+                contract: self.staking.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(msg)?,
+            },
+            &[],
+        )
+    }
+
+    pub fn stake(&mut self, sender: &str, amount: u128, lock_type: u64) -> AnyResult<AppResponse> {
+        self.send(sender, amount, &stake_msg(lock_type))
+    }
+
+    pub fn deposit_reward(&mut self, amount: u128) -> AnyResult<AppResponse> {
+        self.send(OWNER, amount, &ReceiveMsg::DepositReward {})
+    }
+
+    pub fn execute(&mut self, sender: &str, msg: ExecuteMsg) -> AnyResult<AppResponse> {
+        let staking = self.staking.clone();
+        self.app.execute_contract(Addr::unchecked(sender), staking, &msg, &[])
+    }
+
+    pub fn execute_with_funds(&mut self, sender: &str, msg: ExecuteMsg, funds: &[Coin]) -> AnyResult<AppResponse> {
+        let staking = self.staking.clone();
+        self.app.execute_contract(Addr::unchecked(sender), staking, &msg, funds)
+    }
+
+    pub fn claim(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.execute(sender, claim_msg(None, None))
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: QueryMsg) -> T {
+        self.app.wrap().query_wasm_smart(self.staking.clone(), &msg).unwrap()
+    }
+
+    pub fn positions(&self, address: &str) -> Vec<StakerInfo> {
+        self.query(QueryMsg::Staker {
+            address: Addr::unchecked(address),
+        })
+    }
+
+    pub fn balance(&self, address: &str) -> u128 {
+        let response: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                self.token.clone(),
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        response.balance.u128()
+    }
+
+    // the value of an attribute the staking contract emitted
+    pub fn attr(&self, response: &AppResponse, key: &str) -> String {
+        response
+            .events
+            .iter()
+            .filter(|event| {
+                event.ty == "wasm"
+                    && event
+                        .attributes
+                        .iter()
+                        .any(|attribute| attribute.key == "_contract_addr" && attribute.value == self.staking.as_str())
+            })
+            .flat_map(|event| event.attributes.iter())
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.clone())
+            .unwrap_or_else(|| panic!("missing attribute {}", key))
+    }
+
+    pub fn instantiate_receiver(&mut self) -> Addr {
+        let code_id = self.app.store_code(receiver_contract());
+        self.app
+            .instantiate_contract(code_id, Addr::unchecked(OWNER), &Empty {}, &[], "receiver", None)
+            .unwrap()
+    }
+
+    pub fn received(&self, receiver: &Addr) -> Vec<Cw20ReceiveMsg> {
+        self.app.wrap().query_wasm_smart(receiver, &Empty {}).unwrap()
+    }
+
+    pub fn native_balance(&self, address: &str) -> u128 {
+        self.app.wrap().query_balance(address, BONUS_DENOM).unwrap().amount.u128()
+    }
+}
+
+pub fn stake_msg(lock_type: u64) -> ReceiveMsg {
+    ReceiveMsg::Stake {
+        lock_type,
+        lock_seconds: None,
+        curation: None,
+        beneficiary: None,
+        unstake_not_before: None,
+    }
+}
+
+pub fn claim_msg(ids: Option<Vec<u64>>, max_amount: Option<u128>) -> ExecuteMsg {
+    ExecuteMsg::ClaimReward {
+        distribution: None,
+        owner: None,
+        recipient: None,
+        ids,
+        max_amount: max_amount.map(Uint128::new),
+    }
+}
+
+pub fn assert_error(result: AnyResult<AppResponse>, expected: ContractError) {
+    let err = result.unwrap_err();
+    assert_eq!(err.downcast::<ContractError>().unwrap(), expected);
+}
+
+// what update_reward pays a matured position for the time it was staked, rounded the same way
+pub fn reward_for(amount: u128, apy: u64, seconds: u64) -> u128 {
+    let per_interval = amount * apy as u128 / 10000 * DAY as u128 / (365 * DAY) as u128;
+    per_interval * seconds as u128 / DAY as u128
+}
+
+// what settling a position credits for the given time, exact to the second unlike reward_for
+pub fn settled(amount: u128, apy: u64, seconds: u64) -> u128 {
+    amount * apy as u128 * seconds as u128 / (10000 * ONE_YEAR_SECONDS) as u128
+}
@@ -0,0 +1,193 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use rize_staking::constants::{DAYS_30_APY, DAYS_720_APY};
+use rize_staking::msg::{CuratedListResponse, CuratedTargetResponse, CurationTarget, ExecuteMsg, QueryMsg, ReceiveMsg};
+use rize_staking::ContractError;
+
+const ARTIST: &str = "artist";
+const COLLECTION_PAYOUT: &str = "collection_payout";
+
+fn curated_stake(lock_type: u64, curation: CurationTarget) -> ReceiveMsg {
+    ReceiveMsg::Stake {
+        lock_type,
+        lock_seconds: None,
+        curation: Some(curation),
+        beneficiary: None,
+        unstake_not_before: None,
+    }
+}
+
+fn artist(address: &str) -> CurationTarget {
+    CurationTarget::Artist {
+        address: Addr::unchecked(address),
+    }
+}
+
+fn curated(suite: &Suite, target: CurationTarget) -> CuratedTargetResponse {
+    suite.query(QueryMsg::CuratedTarget { target })
+}
+
+#[test]
+fn curated_stake_is_tracked_per_target() {
+    let mut suite = Suite::new();
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::RegisterCollection {
+                id: "genesis".to_string(),
+                payout: Addr::unchecked(COLLECTION_PAYOUT),
+            },
+        )
+        .unwrap();
+    suite.send(ALICE, 1_000, &curated_stake(0, artist(ARTIST))).unwrap();
+    suite.send(BOB, 3_000, &curated_stake(0, CurationTarget::Collection { id: "genesis".to_string() })).unwrap();
+    suite.send(CAROL, 500, &curated_stake(0, artist(ARTIST))).unwrap();
+
+    assert_eq!(curated(&suite, artist(ARTIST)).stake, Uint128::new(1_500));
+    let top: CuratedListResponse = suite.query(QueryMsg::TopCurated { limit: None });
+    let stakes: Vec<u128> = top.targets.iter().map(|target| target.stake.u128()).collect();
+    assert_eq!(stakes, vec![3_000, 1_500]);
+
+    assert_error(
+        suite.send(ALICE, 1_000, &curated_stake(0, CurationTarget::Collection { id: "unknown".to_string() })),
+        ContractError::UnknownCollection {},
+    );
+}
+
+#[test]
+fn artists_are_stored_by_their_normalized_address() {
+    let mut suite = Suite::new();
+    suite.send(ALICE, 1_000, &curated_stake(0, artist(ARTIST))).unwrap();
+    // another spelling of the same artist never gets a key of its own
+    assert!(suite.send(BOB, 1_000, &curated_stake(0, artist("ARTIST"))).is_err());
+
+    let top: CuratedListResponse = suite.query(QueryMsg::TopCurated { limit: None });
+    assert_eq!(top.targets.len(), 1);
+    assert_eq!(top.targets[0].target, artist(ARTIST));
+    assert_eq!(suite.positions(ALICE)[0].curation, Some(artist(ARTIST)));
+}
+
+#[test]
+fn curated_target_earns_its_share_of_the_claim() {
+    let mut suite = Suite::new();
+    suite.execute(OWNER, ExecuteMsg::UpdateCurationShare { share: 10 }).unwrap();
+    suite.deposit_reward(1_000_000).unwrap();
+    suite.send(ALICE, 1_000_000, &curated_stake(0, artist(ARTIST))).unwrap();
+    suite.advance(31 * DAY);
+
+    let before = suite.balance(ALICE);
+    suite.claim(ALICE).unwrap();
+
+    let reward = reward_for(1_000_000, DAYS_30_APY, 31 * DAY);
+    let share = reward * 10 / 100;
+    assert_eq!(suite.balance(ARTIST), share);
+    assert_eq!(suite.balance(ALICE), before + reward - share);
+    assert_eq!(curated(&suite, artist(ARTIST)).earned, Uint128::new(share));
+}
+
+#[test]
+fn set_curation_moves_the_stake() {
+    let mut suite = Suite::new();
+    suite.send(ALICE, 1_000, &curated_stake(0, artist(ARTIST))).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+    // the position is looked up among the sender's own
+    assert_error(
+        suite.execute(
+            BOB,
+            ExecuteMsg::SetCuration {
+                id,
+                curation: Some(artist(BOB)),
+            },
+        ),
+        ContractError::NoStaked {},
+    );
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::SetCuration {
+                id,
+                curation: Some(artist(BOB)),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(curated(&suite, artist(ARTIST)).stake, Uint128::zero());
+    assert_eq!(curated(&suite, artist(BOB)).stake, Uint128::new(1_000));
+}
+
+#[test]
+fn switching_targets_pays_the_old_target_its_share_first() {
+    let mut suite = Suite::new();
+    suite.execute(OWNER, ExecuteMsg::UpdateCurationShare { share: 10 }).unwrap();
+    suite.deposit_reward(1_000_000).unwrap();
+    suite.send(ALICE, 1_000_000, &curated_stake(0, artist(ARTIST))).unwrap();
+
+    // the position matured under the first artist before it moves to another one
+    suite.advance(31 * DAY);
+    let id = suite.positions(ALICE)[0].id;
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::SetCuration {
+                id,
+                curation: Some(artist(CAROL)),
+            },
+        )
+        .unwrap();
+    let before = settled(1_000_000, DAYS_30_APY, 31 * DAY);
+    let before_share = before * 10 / 100;
+    assert_eq!(curated(&suite, artist(ARTIST)).earned, Uint128::new(before_share));
+    assert_eq!(suite.positions(ALICE)[0].reward, Uint128::zero());
+
+    // the old artist claims what it earned, the new one only shares what came after the switch
+    suite.claim(ARTIST).unwrap();
+    assert_eq!(suite.balance(ARTIST), before_share);
+    let balance = suite.balance(ALICE);
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE) - balance, before - before_share);
+
+    suite.advance(31 * DAY);
+    let balance = suite.balance(ALICE);
+    suite.claim(ALICE).unwrap();
+    let after = reward_for(1_000_000, DAYS_30_APY, 31 * DAY);
+    let after_share = after * 10 / 100;
+    assert_eq!(suite.balance(CAROL) - INITIAL_BALANCE, after_share);
+    assert_eq!(curated(&suite, artist(CAROL)).earned, Uint128::new(after_share));
+    assert_eq!(suite.balance(ALICE) - balance, after - after_share);
+}
+
+#[test]
+fn switching_targets_does_not_pay_before_maturity() {
+    let mut suite = Suite::new();
+    suite.execute(OWNER, ExecuteMsg::UpdateCurationShare { share: 10 }).unwrap();
+    suite.deposit_reward(1_000_000_000).unwrap();
+    suite.send(ALICE, 1_000_000, &curated_stake(7, artist(ARTIST))).unwrap();
+
+    suite.advance(30 * DAY);
+    let id = suite.positions(ALICE)[0].id;
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::SetCuration {
+                id,
+                curation: Some(artist(CAROL)),
+            },
+        )
+        .unwrap();
+    // the reward so far stays on the position until the 720 day lock runs out
+    assert_error(suite.claim(ALICE), ContractError::NoReward {});
+    assert_eq!(curated(&suite, artist(ARTIST)).earned, Uint128::zero());
+    let locked = settled(1_000_000, DAYS_720_APY, 30 * DAY);
+    assert_eq!(suite.positions(ALICE)[0].locked_reward.u128(), locked);
+
+    // on maturity it is paid with the curation the position has then
+    suite.advance(690 * DAY);
+    let balance = suite.balance(ALICE);
+    suite.claim(ALICE).unwrap();
+    let reward = locked + reward_for(1_000_000, DAYS_720_APY, 690 * DAY);
+    let share = reward * 10 / 100;
+    assert_eq!(curated(&suite, artist(CAROL)).earned, Uint128::new(share));
+    assert_eq!(suite.balance(ALICE) - balance, reward - share);
+}
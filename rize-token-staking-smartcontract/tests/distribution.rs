@@ -0,0 +1,97 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Addr;
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use rize_staking::constants::DAYS_30_APY;
+use rize_staking::msg::{ExecuteMsg, RewardDistribution};
+use rize_staking::ContractError;
+
+const CHARITY: &str = "charity";
+const ARTISTS: &str = "artists";
+
+fn claim_with(suite: &mut Suite, charity: u64, burn: u64, artists: u64) -> anyhow::Result<cw_multi_test::AppResponse> {
+    suite.execute(
+        ALICE,
+        ExecuteMsg::ClaimReward {
+            distribution: Some(RewardDistribution { charity, burn, artists }),
+            owner: None,
+            recipient: None,
+            ids: None,
+            max_amount: None,
+        },
+    )
+}
+
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::UpdateDistributionWallets {
+                charity_wallet: Addr::unchecked(CHARITY),
+                artists_wallet: Addr::unchecked(ARTISTS),
+            },
+        )
+        .unwrap();
+    suite.deposit_reward(1_000_000).unwrap();
+    suite.stake(ALICE, 1_000_000, 0).unwrap();
+    suite.advance(31 * DAY);
+    suite
+}
+
+fn total_supply(suite: &Suite) -> u128 {
+    let info: TokenInfoResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(suite.token.clone(), &Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    info.total_supply.u128()
+}
+
+#[test]
+fn reward_is_split_between_the_wallets() {
+    let mut suite = setup();
+    let supply = total_supply(&suite);
+    let before = suite.balance(ALICE);
+
+    claim_with(&mut suite, 10, 20, 30).unwrap();
+
+    let reward = reward_for(1_000_000, DAYS_30_APY, 31 * DAY);
+    let charity = reward * 10 / 100;
+    let burn = reward * 20 / 100;
+    let artists = reward * 30 / 100;
+    assert_eq!(suite.balance(CHARITY), charity);
+    assert_eq!(suite.balance(ARTISTS), artists);
+    assert_eq!(total_supply(&suite), supply - burn);
+    assert_eq!(suite.balance(ALICE), before + reward - charity - burn - artists);
+}
+
+#[test]
+fn shares_over_100_percent_are_rejected() {
+    let mut suite = setup();
+    assert_error(claim_with(&mut suite, 50, 30, 21), ContractError::InvalidDistribution {});
+    assert_error(claim_with(&mut suite, 101, 0, 0), ContractError::InvalidDistribution {});
+}
+
+#[test]
+fn shares_that_would_wrap_are_rejected() {
+    let mut suite = setup();
+    // u64::MAX - 50 + 51 wraps to 0 when summed unchecked
+    assert_error(claim_with(&mut suite, u64::MAX - 50, 51, 0), ContractError::InvalidDistribution {});
+    assert_error(claim_with(&mut suite, 0, u64::MAX, u64::MAX), ContractError::InvalidDistribution {});
+
+    claim_with(&mut suite, 0, 0, 0).unwrap();
+    assert!(suite.balance(ALICE) > INITIAL_BALANCE - 1_000_000);
+}
+
+#[test]
+fn charity_without_wallets_is_rejected() {
+    let mut suite = Suite::new();
+    suite.deposit_reward(1_000_000).unwrap();
+    suite.stake(ALICE, 1_000_000, 0).unwrap();
+    suite.advance(31 * DAY);
+    assert_error(claim_with(&mut suite, 10, 0, 0), ContractError::DistributionNotConfigured {});
+    // burning needs no wallet
+    claim_with(&mut suite, 0, 10, 0).unwrap();
+}
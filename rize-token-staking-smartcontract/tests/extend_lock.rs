@@ -0,0 +1,51 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use rize_staking::constants::{DAYS_180_APY, DAYS_180_SECONDS, DAYS_90_APY};
+use rize_staking::msg::{ClaimableBalanceResponse, ExecuteMsg, QueryMsg};
+use rize_staking::ContractError;
+
+const AMOUNT: u128 = 1_000_000;
+
+fn claimable(suite: &Suite, address: &str) -> u128 {
+    let response: ClaimableBalanceResponse = suite.query(QueryMsg::ClaimableBalance {
+        address: Addr::unchecked(address),
+    });
+    response.amount.u128()
+}
+
+#[test]
+fn extending_settles_at_the_old_tier_and_locks_again_from_now() {
+    let mut suite = Suite::new();
+    suite.deposit_reward(INITIAL_BALANCE).unwrap();
+    suite.stake(ALICE, AMOUNT, 2).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+    suite.advance(40 * DAY);
+
+    // the same or a shorter tier is no extension
+    for new_tier in [1, 2] {
+        assert_error(
+            suite.execute(ALICE, ExecuteMsg::ExtendLock { id, new_tier }),
+            ContractError::InvalidInput {},
+        );
+    }
+
+    suite.execute(ALICE, ExecuteMsg::ExtendLock { id, new_tier: 4 }).unwrap();
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.lock_type, DAYS_180_SECONDS);
+    assert_eq!(position.last_time, suite.now());
+    let old_tier = settled(AMOUNT, DAYS_90_APY, 40 * DAY);
+    assert_eq!(position.locked_reward.u128(), old_tier);
+
+    // the 180 day lock runs from the extension, the time after it earns the new tier
+    let unstake = ExecuteMsg::Unstake {
+        index: 0,
+        amount: Uint128::new(AMOUNT),
+    };
+    suite.advance(179 * DAY);
+    assert_error(suite.execute(ALICE, unstake.clone()), ContractError::StillLocked {});
+    suite.advance(DAY);
+    suite.execute(ALICE, unstake).unwrap();
+    assert_eq!(claimable(&suite, ALICE), old_tier + settled(AMOUNT, DAYS_180_APY, 180 * DAY));
+}
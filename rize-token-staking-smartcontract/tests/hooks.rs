@@ -0,0 +1,143 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Uint128,
+};
+use cw20::Cw20ExecuteMsg;
+use cw_multi_test::{ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use rize_staking::constants::{DAYS_30_SECONDS, DAYS_720_SECONDS};
+use rize_staking::msg::{
+    ExecuteMsg, LiquidPoolResponse, QueryMsg, ReceiveMsg, StakeChangedExecuteMsg, StakeChangedHookMsg,
+};
+
+const AMOUNT: u128 = 1_000_000;
+
+// a hook contract that keeps every message it was sent
+const RECEIVED: Item<Vec<StakeChangedHookMsg>> = Item::new("received");
+
+fn hook_instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    RECEIVED.save(deps.storage, &vec![])?;
+    Ok(Response::new())
+}
+
+fn hook_execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: StakeChangedExecuteMsg) -> StdResult<Response> {
+    let StakeChangedExecuteMsg::StakeChangeHook(msg) = msg;
+    RECEIVED.update(deps.storage, |mut received| -> StdResult<_> {
+        received.push(msg);
+        Ok(received)
+    })?;
+    Ok(Response::new())
+}
+
+fn hook_query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_binary(&RECEIVED.load(deps.storage)?)
+}
+
+// a suite with the recording contract registered as the only hook
+fn setup() -> (Suite, Addr) {
+    let mut suite = Suite::new();
+    let code_id = suite
+        .app
+        .store_code(Box::new(ContractWrapper::new(hook_execute, hook_instantiate, hook_query)));
+    let hook = suite
+        .app
+        .instantiate_contract(code_id, Addr::unchecked(OWNER), &Empty {}, &[], "hook", None)
+        .unwrap();
+    suite.execute(OWNER, ExecuteMsg::AddHook { addr: hook.clone() }).unwrap();
+    (suite, hook)
+}
+
+fn received(suite: &Suite, hook: &Addr) -> Vec<StakeChangedHookMsg> {
+    suite.app.wrap().query_wasm_smart(hook, &Empty {}).unwrap()
+}
+
+#[test]
+fn stake_unstake_and_extend_lock_reach_the_hook() {
+    let (mut suite, hook) = setup();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+    suite.execute(ALICE, ExecuteMsg::ExtendLock { id, new_tier: 7 }).unwrap();
+    suite.advance(721 * DAY);
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::Unstake {
+                index: 0,
+                amount: Uint128::new(AMOUNT),
+            },
+        )
+        .unwrap();
+
+    let alice = Addr::unchecked(ALICE);
+    assert_eq!(
+        received(&suite, &hook),
+        vec![
+            StakeChangedHookMsg::Stake {
+                addr: alice.clone(),
+                amount: Uint128::new(AMOUNT),
+                lock_type: DAYS_30_SECONDS,
+            },
+            StakeChangedHookMsg::ExtendLock {
+                addr: alice.clone(),
+                amount: Uint128::new(AMOUNT),
+                lock_type: DAYS_720_SECONDS,
+            },
+            StakeChangedHookMsg::Unstake {
+                addr: alice,
+                amount: Uint128::new(AMOUNT),
+            },
+        ]
+    );
+}
+
+#[test]
+fn liquid_stake_and_redeem_reach_the_hook() {
+    let (mut suite, hook) = setup();
+    let code_id = suite.cw20_code_id;
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::CreateLiquidToken {
+                code_id,
+                name: "Staked Rize".to_string(),
+                symbol: "stRIZE".to_string(),
+                apy: None,
+            },
+        )
+        .unwrap();
+    let pool: LiquidPoolResponse = suite.query(QueryMsg::LiquidPool {});
+    suite.send(ALICE, AMOUNT, &ReceiveMsg::LiquidStake {}).unwrap();
+
+    let staking = suite.staking.to_string();
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ALICE),
+            pool.token.unwrap(),
+            &Cw20ExecuteMsg::Send {
+                contract: staking,
+                amount: Uint128::new(AMOUNT),
+                msg: to_binary(&ReceiveMsg::Redeem {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let alice = Addr::unchecked(ALICE);
+    assert_eq!(
+        received(&suite, &hook),
+        vec![
+            StakeChangedHookMsg::Stake {
+                addr: alice.clone(),
+                amount: Uint128::new(AMOUNT),
+                lock_type: 0,
+            },
+            StakeChangedHookMsg::Unstake {
+                addr: alice,
+                amount: Uint128::new(AMOUNT),
+            },
+        ]
+    );
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use rize_staking::msg::{ExecuteMsg, LeaderboardResponse, QueryMsg, RankResponse};
+
+fn leaderboard(suite: &Suite) -> Vec<(String, u128, Option<u8>)> {
+    let response: LeaderboardResponse = suite.query(QueryMsg::Leaderboard { limit: None });
+    response
+        .ranks
+        .into_iter()
+        .map(|rank| (rank.address.to_string(), rank.weight.u128(), rank.rank))
+        .collect()
+}
+
+#[test]
+fn ranks_follow_weighted_stake() {
+    let mut suite = Suite::new();
+    // a 720 day lock counts in full, a 30 day lock for 30 / 720 of its amount
+    suite.stake(ALICE, 1_000, 7).unwrap();
+    suite.stake(BOB, 12_000, 0).unwrap();
+    suite.stake(CAROL, 400, 7).unwrap();
+
+    assert_eq!(
+        leaderboard(&suite),
+        vec![
+            (ALICE.to_string(), 1_000, Some(1)),
+            (BOB.to_string(), 500, Some(2)),
+            (CAROL.to_string(), 400, Some(3)),
+        ]
+    );
+
+    let rank: RankResponse = suite.query(QueryMsg::Rank {
+        address: Addr::unchecked(BOB),
+    });
+    assert_eq!(rank.weight, Uint128::new(500));
+    assert_eq!(rank.rank, Some(2));
+}
+
+#[test]
+fn ties_break_on_the_lower_address() {
+    let mut suite = Suite::new();
+    suite.stake(CAROL, 500, 7).unwrap();
+    suite.stake(BOB, 500, 7).unwrap();
+
+    assert_eq!(
+        leaderboard(&suite),
+        vec![(BOB.to_string(), 500, Some(1)), (CAROL.to_string(), 500, Some(2))]
+    );
+}
+
+#[test]
+fn unstake_removes_the_staker_from_the_leaderboard() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, 1_000, 7).unwrap();
+    suite.stake(BOB, 12_000, 0).unwrap();
+
+    suite.advance(31 * DAY);
+    suite
+        .execute(
+            BOB,
+            ExecuteMsg::Unstake {
+                index: 0,
+                amount: Uint128::new(12_000),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(leaderboard(&suite), vec![(ALICE.to_string(), 1_000, Some(1))]);
+    let rank: RankResponse = suite.query(QueryMsg::Rank {
+        address: Addr::unchecked(BOB),
+    });
+    assert_eq!(rank.rank, None);
+}
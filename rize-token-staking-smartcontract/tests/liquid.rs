@@ -0,0 +1,100 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{to_binary, Addr, Uint128};
+use cw20::Cw20ExecuteMsg;
+use cw_multi_test::{AppResponse, Executor};
+use rize_staking::msg::{ClaimableBalanceResponse, ExecuteMsg, LiquidPoolResponse, QueryMsg, ReceiveMsg};
+
+const AMOUNT: u128 = 1_000_000;
+
+// the stRIZE token created by the staking contract, with alice holding AMOUNT of it
+fn setup() -> (Suite, Addr) {
+    let mut suite = Suite::new();
+    let code_id = suite.cw20_code_id;
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::CreateLiquidToken {
+                code_id,
+                name: "Staked Rize".to_string(),
+                symbol: "stRIZE".to_string(),
+                apy: None,
+            },
+        )
+        .unwrap();
+    let pool: LiquidPoolResponse = suite.query(QueryMsg::LiquidPool {});
+    suite.send(ALICE, AMOUNT, &ReceiveMsg::LiquidStake {}).unwrap();
+    (suite, pool.token.unwrap())
+}
+
+fn redeem(suite: &mut Suite, liquid_token: &Addr, amount: u128) -> AppResponse {
+    let staking = suite.staking.to_string();
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ALICE),
+            liquid_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: staking,
+                amount: Uint128::new(amount),
+                msg: to_binary(&ReceiveMsg::Redeem {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap()
+}
+
+fn claimable(suite: &Suite, address: &str) -> u128 {
+    let response: ClaimableBalanceResponse = suite.query(QueryMsg::ClaimableBalance {
+        address: Addr::unchecked(address),
+    });
+    response.amount.u128()
+}
+
+#[test]
+fn an_empty_reward_pool_still_returns_the_principal() {
+    let (mut suite, liquid_token) = setup();
+    suite.advance(365 * DAY);
+
+    let response = redeem(&mut suite, &liquid_token, AMOUNT);
+    assert_eq!(suite.attr(&response, "principal"), AMOUNT.to_string());
+    assert_eq!(suite.attr(&response, "reward"), "0");
+    let shortfall: u128 = suite.attr(&response, "shortfall").parse().unwrap();
+    assert!(shortfall > 0);
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE);
+    assert_eq!(claimable(&suite, ALICE), shortfall);
+
+    // the yield is paid once the pool is refilled
+    suite.deposit_reward(shortfall).unwrap();
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE + shortfall);
+    assert_eq!(claimable(&suite, ALICE), 0);
+}
+
+#[test]
+fn a_short_reward_pool_pays_what_it_holds() {
+    let (mut suite, liquid_token) = setup();
+    suite.deposit_reward(1_000).unwrap();
+    suite.advance(365 * DAY);
+
+    let response = redeem(&mut suite, &liquid_token, AMOUNT);
+    assert_eq!(suite.attr(&response, "reward"), "1000");
+    let shortfall: u128 = suite.attr(&response, "shortfall").parse().unwrap();
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE + 1_000);
+    assert_eq!(claimable(&suite, ALICE), shortfall);
+}
+
+#[test]
+fn a_funded_pool_pays_the_full_yield() {
+    let (mut suite, liquid_token) = setup();
+    suite.deposit_reward(AMOUNT).unwrap();
+    suite.advance(365 * DAY);
+
+    let response = redeem(&mut suite, &liquid_token, AMOUNT);
+    let reward: u128 = suite.attr(&response, "reward").parse().unwrap();
+    assert!(reward > 0);
+    assert_eq!(suite.attr(&response, "shortfall"), "0");
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE + reward);
+    assert_eq!(claimable(&suite, ALICE), 0);
+}
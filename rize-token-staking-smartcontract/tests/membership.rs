@@ -0,0 +1,129 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use rize_staking::constants::MIN_ON_BEHALF_STAKE;
+use rize_staking::msg::{
+    ExecuteMsg, MembersAtLevelResponse, MembershipLevel, MembershipLevelResponse, QueryMsg, ReceiveMsg,
+};
+
+const AMOUNT: u128 = 1_000_000;
+
+// silver asks for any stake locked 60 more days, gold for ten times as much locked a year
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::UpdateMembershipLevels {
+                levels: vec![
+                    MembershipLevel {
+                        name: "silver".to_string(),
+                        min_amount: Uint128::new(AMOUNT),
+                        min_lock_seconds: 60 * DAY,
+                    },
+                    MembershipLevel {
+                        name: "gold".to_string(),
+                        min_amount: Uint128::new(10 * AMOUNT),
+                        min_lock_seconds: 360 * DAY,
+                    },
+                ],
+            },
+        )
+        .unwrap();
+    suite
+}
+
+fn level(suite: &Suite, address: &str) -> Option<String> {
+    let response: MembershipLevelResponse = suite.query(QueryMsg::MembershipLevel {
+        address: Addr::unchecked(address),
+    });
+    response.level
+}
+
+fn members(suite: &Suite, level: &str, start_after: Option<Addr>, limit: Option<u32>) -> MembersAtLevelResponse {
+    suite.query(QueryMsg::MembersAtLevel {
+        level: level.to_string(),
+        start_after: start_after.map(String::from),
+        limit,
+    })
+}
+
+#[test]
+fn levels_follow_the_amount_and_the_lock_left() {
+    let mut suite = setup();
+    suite.stake(ALICE, 10 * AMOUNT, 7).unwrap();
+    suite.stake(BOB, 10 * AMOUNT - 1, 7).unwrap();
+    suite.stake(CAROL, AMOUNT, 1).unwrap();
+
+    assert_eq!(level(&suite, ALICE), Some("gold".to_string()));
+    // just below the gold amount
+    assert_eq!(level(&suite, BOB), Some("silver".to_string()));
+    assert_eq!(level(&suite, CAROL), Some("silver".to_string()));
+    assert_eq!(level(&suite, OWNER), None);
+    assert_eq!(
+        members(&suite, "silver", None, None).members,
+        vec![Addr::unchecked(BOB), Addr::unchecked(CAROL)]
+    );
+
+    // a day later the 60 day lock has less than 60 days left
+    suite.advance(DAY);
+    assert_eq!(level(&suite, CAROL), None);
+
+    // once less than a year is left gold falls back to silver
+    suite.advance(360 * DAY);
+    assert_eq!(level(&suite, ALICE), Some("silver".to_string()));
+    let gold = members(&suite, "gold", None, None);
+    assert!(gold.members.is_empty());
+    assert_eq!(gold.next_start_after, None);
+}
+
+#[test]
+fn members_are_paged_with_a_bounded_scan() {
+    let mut suite = setup();
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::UpdateStakeFunder {
+                funder: Addr::unchecked(FUNDER),
+                authorized: true,
+            },
+        )
+        .unwrap();
+    // a crowd of stakers below every level, then one silver member behind them
+    for i in 0..350 {
+        suite
+            .send(
+                FUNDER,
+                MIN_ON_BEHALF_STAKE,
+                &ReceiveMsg::Stake {
+                    lock_type: 0,
+                    lock_seconds: None,
+                    curation: None,
+                    beneficiary: Some(Addr::unchecked(format!("a{:03}", i))),
+                    unstake_not_before: None,
+                },
+            )
+            .unwrap();
+    }
+    suite.stake(BOB, AMOUNT, 2).unwrap();
+    suite.stake(CAROL, AMOUNT, 2).unwrap();
+
+    // the first page stops after the scan cap without a match and says where to go on
+    let page = members(&suite, "silver", None, Some(1));
+    assert!(page.members.is_empty());
+    let cursor = page.next_start_after.unwrap();
+    assert_eq!(cursor, Addr::unchecked("a299"));
+
+    let page = members(&suite, "silver", Some(cursor), Some(1));
+    assert_eq!(page.members, vec![Addr::unchecked(BOB)]);
+    assert_eq!(page.next_start_after, Some(Addr::unchecked(BOB)));
+
+    let page = members(&suite, "silver", page.next_start_after, Some(1));
+    assert_eq!(page.members, vec![Addr::unchecked(CAROL)]);
+
+    // the last page has nothing left to look at
+    let page = members(&suite, "silver", Some(Addr::unchecked(CAROL)), None);
+    assert!(page.members.is_empty());
+    assert_eq!(page.next_start_after, None);
+}
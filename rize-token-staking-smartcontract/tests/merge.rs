@@ -0,0 +1,105 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Uint128;
+use rize_staking::constants::DAYS_720_APY;
+use rize_staking::msg::{ExecuteMsg, MergeUnlock, StakerInfo};
+use rize_staking::ContractError;
+
+const AMOUNT: u128 = 1_000_000;
+
+fn position(suite: &Suite, id: u64) -> StakerInfo {
+    suite.positions(ALICE).into_iter().find(|position| position.id == id).unwrap()
+}
+
+// two 720 day positions of alice staked 100 days apart, the ids of the earlier and the later one
+fn setup(mode: MergeUnlock) -> (Suite, u64, u64) {
+    let mut suite = Suite::new();
+    suite.execute(OWNER, ExecuteMsg::UpdateMergeUnlock { mode }).unwrap();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    suite.advance(100 * DAY);
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    let positions = suite.positions(ALICE);
+    (suite, positions[0].id, positions[1].id)
+}
+
+#[test]
+fn latest_keeps_the_time_the_earlier_position_had_staked() {
+    let (mut suite, earlier, later) = setup(MergeUnlock::Latest);
+    let start = position(&suite, later).last_time;
+    suite.advance(DAY);
+
+    suite
+        .execute(ALICE, ExecuteMsg::MergePositions { ids: vec![earlier, later] })
+        .unwrap();
+    let merged = position(&suite, earlier);
+    assert_eq!(merged.amount.u128(), 2 * AMOUNT);
+    assert_eq!(merged.last_time, start);
+    assert_eq!(merged.settled_time, Some(suite.now()));
+    assert_eq!(
+        merged.locked_reward.u128(),
+        settled(AMOUNT, DAYS_720_APY, 101 * DAY) + settled(AMOUNT, DAYS_720_APY, DAY)
+    );
+}
+
+#[test]
+fn weighted_average_only_moves_the_unlock_time() {
+    let (mut suite, earlier, later) = setup(MergeUnlock::WeightedAverage);
+    let first = position(&suite, earlier).last_time;
+
+    suite
+        .execute(ALICE, ExecuteMsg::MergePositions { ids: vec![earlier, later] })
+        .unwrap();
+    let merged = position(&suite, earlier);
+    assert_eq!(merged.last_time, first + 50 * DAY);
+    assert_eq!(merged.locked_reward.u128(), settled(AMOUNT, DAYS_720_APY, 100 * DAY));
+
+    // the time before the merge is not paid a second time once the merged position matures
+    suite.advance(720 * DAY);
+    suite.deposit_reward(INITIAL_BALANCE).unwrap();
+    let balance = suite.balance(ALICE);
+    suite.claim(ALICE).unwrap();
+    let paid = suite.balance(ALICE) - balance;
+    assert_eq!(
+        paid,
+        settled(AMOUNT, DAYS_720_APY, 100 * DAY) + reward_for(2 * AMOUNT, DAYS_720_APY, 720 * DAY)
+    );
+}
+
+#[test]
+fn a_long_matured_position_does_not_shorten_the_lock_of_a_new_one() {
+    let mut suite = Suite::new();
+    suite.execute(OWNER, ExecuteMsg::UpdateMergeUnlock { mode: MergeUnlock::WeightedAverage }).unwrap();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    suite.advance(400 * DAY);
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let positions = suite.positions(ALICE);
+
+    suite
+        .execute(ALICE, ExecuteMsg::MergePositions { ids: vec![positions[0].id, positions[1].id] })
+        .unwrap();
+    // the matured position counts as unlocking now, so half the new 30 day lock is left
+    let merged = position(&suite, positions[0].id);
+    assert_eq!(merged.last_time, suite.now() - 15 * DAY);
+    let unstake = ExecuteMsg::Unstake {
+        index: 0,
+        amount: Uint128::new(2 * AMOUNT),
+    };
+    assert_error(suite.execute(ALICE, unstake.clone()), ContractError::StillLocked {});
+    suite.advance(15 * DAY);
+    suite.execute(ALICE, unstake).unwrap();
+}
+
+#[test]
+fn other_matured_positions_keep_their_lock() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let matured = suite.positions(ALICE)[0].clone();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    suite.advance(40 * DAY);
+
+    let ids = suite.positions(ALICE)[1..].iter().map(|position| position.id).collect();
+    suite.execute(ALICE, ExecuteMsg::MergePositions { ids }).unwrap();
+    assert_eq!(position(&suite, matured.id), matured);
+}
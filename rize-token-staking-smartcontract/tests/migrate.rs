@@ -0,0 +1,144 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Uint128};
+use cw721::NumTokensResponse;
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use rize_staking::constants::DAYS_720_SECONDS;
+use rize_staking::msg::{ExecuteMsg, InstantiateMsg, LeaderboardResponse, MigrateMsg, QueryMsg, StakerInfo};
+use rize_staking::state::STAKERS;
+use rize_staking::ContractError;
+
+// a deployment from before position ids, the leaderboard and the voting power history
+fn legacy_instantiate(mut deps: DepsMut, env: Env, info: MessageInfo, msg: InstantiateMsg) -> StdResult<Response> {
+    let response = rize_staking::contract::instantiate(deps.branch(), env.clone(), info, msg)?;
+    cw2::set_contract_version(deps.storage, "rize-staking", "0.1.0")?;
+    for address in [ALICE, BOB, CAROL] {
+        let address = Addr::unchecked(address);
+        let position = StakerInfo {
+            id: 0,
+            address: address.clone(),
+            amount: Uint128::new(1_000),
+            reward: Uint128::zero(),
+            last_time: env.block.time.seconds(),
+            lock_type: DAYS_720_SECONDS,
+            curation: None,
+            boost: None,
+            funder: None,
+            unstake_not_before: None,
+            vesting: None,
+            apy: None,
+            settled_time: None,
+            locked_reward: Uint128::zero(),
+        };
+        STAKERS.save(deps.storage, address, &vec![position])?;
+    }
+    Ok(response)
+}
+
+fn legacy_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        rize_staking::contract::execute,
+        legacy_instantiate,
+        rize_staking::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn legacy_suite() -> Suite {
+    let mut suite = Suite::new();
+    let code_id = suite.app.store_code(legacy_contract());
+    suite.staking = suite
+        .app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg {
+                rize_token_address: suite.token.clone(),
+                reward_interval: DAY,
+            },
+            &[],
+            "rize-staking",
+            Some(OWNER.to_string()),
+        )
+        .unwrap();
+    let (staking, code_id) = (suite.staking.clone(), suite.staking_code_id);
+    suite
+        .app
+        .migrate_contract(Addr::unchecked(OWNER), staking, &MigrateMsg {}, code_id)
+        .unwrap();
+    suite
+}
+
+fn migrate_batch(suite: &mut Suite, start_after: Option<&str>, limit: u32) -> (String, Option<String>) {
+    let response = suite
+        .execute(
+            OWNER,
+            ExecuteMsg::MigrateBatch {
+                start_after: start_after.map(Addr::unchecked),
+                limit: Some(limit),
+            },
+        )
+        .unwrap();
+    let last = response
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attribute| attribute.key == "last")
+        .map(|attribute| attribute.value.clone());
+    (suite.attr(&response, "migrated"), last)
+}
+
+fn ranked(suite: &Suite) -> Vec<String> {
+    let response: LeaderboardResponse = suite.query(QueryMsg::Leaderboard { limit: None });
+    response.ranks.into_iter().map(|rank| rank.address.to_string()).collect()
+}
+
+fn num_tokens(suite: &Suite) -> u64 {
+    let response: NumTokensResponse = suite.query(QueryMsg::NumTokens {});
+    response.count
+}
+
+#[test]
+fn migrate_only_sets_the_version() {
+    let suite = legacy_suite();
+    let version = cw2::query_contract_info(&suite.app, suite.staking.to_string()).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+    // no staker is rebuilt in the migration itself
+    assert_eq!(suite.positions(ALICE)[0].id, 0);
+    assert!(ranked(&suite).is_empty());
+}
+
+#[test]
+fn stakers_are_rebuilt_page_by_page() {
+    let mut suite = legacy_suite();
+    assert_error(
+        suite.execute(
+            ALICE,
+            ExecuteMsg::MigrateBatch {
+                start_after: None,
+                limit: None,
+            },
+        ),
+        ContractError::Unauthorized {},
+    );
+
+    assert_eq!(migrate_batch(&mut suite, None, 2), ("2".to_string(), Some(BOB.to_string())));
+    assert_ne!(suite.positions(ALICE)[0].id, 0);
+    assert_ne!(suite.positions(BOB)[0].id, 0);
+    assert_eq!(suite.positions(CAROL)[0].id, 0);
+    assert_eq!(ranked(&suite), vec![ALICE.to_string(), BOB.to_string()]);
+    // positions become nfts once they get an id
+    assert_eq!(num_tokens(&suite), 2);
+
+    assert_eq!(migrate_batch(&mut suite, Some(BOB), 2), ("1".to_string(), Some(CAROL.to_string())));
+    assert_eq!(migrate_batch(&mut suite, Some(CAROL), 2), ("0".to_string(), None));
+    assert_eq!(ranked(&suite), vec![ALICE.to_string(), BOB.to_string(), CAROL.to_string()]);
+
+    // a page run twice keeps the ids it assigned
+    let id = suite.positions(ALICE)[0].id;
+    migrate_batch(&mut suite, None, 1);
+    assert_eq!(suite.positions(ALICE)[0].id, id);
+    assert_eq!(num_tokens(&suite), 3);
+}
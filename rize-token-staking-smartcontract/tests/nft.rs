@@ -0,0 +1,200 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use cw721::{ApprovalsResponse, NftInfoResponse, NumTokensResponse, OwnerOfResponse, TokensResponse};
+use cw_multi_test::AppResponse;
+use rize_staking::constants::DAYS_720_SECONDS;
+use rize_staking::msg::{ExecuteMsg, PositionMetadata, QueryMsg, ReceiveMsg};
+use rize_staking::ContractError;
+
+const AMOUNT: u128 = 1_000_000;
+
+fn owner_of(suite: &Suite, id: u64) -> OwnerOfResponse {
+    suite.query(QueryMsg::OwnerOf {
+        token_id: id.to_string(),
+        include_expired: None,
+    })
+}
+
+fn tokens(suite: &Suite, owner: &str) -> Vec<String> {
+    let response: TokensResponse = suite.query(QueryMsg::Tokens {
+        owner: owner.to_string(),
+        start_after: None,
+        limit: None,
+    });
+    response.tokens
+}
+
+fn nft_info(suite: &Suite, id: u64) -> PositionMetadata {
+    let response: NftInfoResponse<PositionMetadata> = suite.query(QueryMsg::NftInfo {
+        token_id: id.to_string(),
+    });
+    response.extension
+}
+
+// the attributes of a cw721 event emitted by the staking contract
+fn nft_event(response: &AppResponse, ty: &str) -> Vec<(String, String)> {
+    response
+        .events
+        .iter()
+        .find(|event| event.ty == format!("wasm-{}", ty))
+        .unwrap_or_else(|| panic!("no {} event", ty))
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.key != "_contract_addr")
+        .map(|attribute| (attribute.key.clone(), attribute.value.clone()))
+        .collect()
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+}
+
+#[test]
+fn nft_info_tells_custom_locks_from_tiers() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    suite
+        .send(
+            ALICE,
+            AMOUNT,
+            &ReceiveMsg::Stake {
+                lock_type: 0,
+                lock_seconds: Some(500 * DAY),
+                curation: None,
+                beneficiary: None,
+                unstake_not_before: None,
+            },
+        )
+        .unwrap();
+    let positions = suite.positions(ALICE);
+
+    let tier = nft_info(&suite, positions[0].id);
+    assert_eq!(tier.tier, Some(7));
+    assert_eq!(tier.lock_seconds, DAYS_720_SECONDS);
+
+    let custom = nft_info(&suite, positions[1].id);
+    assert_eq!(custom.tier, None);
+    assert_eq!(custom.lock_seconds, 500 * DAY);
+    assert_eq!(custom.unlock_time, positions[1].last_time + 500 * DAY);
+    assert_eq!(custom.amount.u128(), AMOUNT);
+}
+
+#[test]
+fn positions_are_minted_and_burned_as_nfts() {
+    let mut suite = Suite::new();
+    let response = suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+    assert_eq!(
+        nft_event(&response, "mint"),
+        pairs(&[("action", "mint"), ("minter", ALICE), ("owner", ALICE), ("token_id", &id.to_string())])
+    );
+    assert_eq!(owner_of(&suite, id).owner, ALICE);
+
+    let response = suite
+        .execute(
+            ALICE,
+            ExecuteMsg::SplitPosition {
+                id,
+                amount: Uint128::new(AMOUNT / 2),
+            },
+        )
+        .unwrap();
+    let split = suite.positions(ALICE)[1].id;
+    assert_eq!(nft_event(&response, "mint")[3], ("token_id".to_string(), split.to_string()));
+    assert_eq!(tokens(&suite, ALICE), vec![id.to_string(), split.to_string()]);
+    let count: NumTokensResponse = suite.query(QueryMsg::NumTokens {});
+    assert_eq!(count.count, 2);
+
+    // merging burns the positions that were folded in
+    let response = suite.execute(ALICE, ExecuteMsg::MergePositions { ids: vec![id, split] }).unwrap();
+    assert_eq!(
+        nft_event(&response, "burn"),
+        pairs(&[("action", "burn"), ("sender", ALICE), ("token_id", &split.to_string())])
+    );
+    assert_eq!(tokens(&suite, ALICE), vec![id.to_string()]);
+
+    // and so does unstaking all of a position
+    suite.advance(31 * DAY);
+    let response = suite
+        .execute(
+            ALICE,
+            ExecuteMsg::Unstake {
+                index: 0,
+                amount: Uint128::new(AMOUNT),
+            },
+        )
+        .unwrap();
+    assert_eq!(nft_event(&response, "burn")[2], ("token_id".to_string(), id.to_string()));
+    assert!(tokens(&suite, ALICE).is_empty());
+    let count: NumTokensResponse = suite.query(QueryMsg::NumTokens {});
+    assert_eq!(count.count, 0);
+}
+
+#[test]
+fn an_approved_spender_transfers_the_nft_right_away() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::Approve {
+                spender: CAROL.to_string(),
+                token_id: id.to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+    let approvals: ApprovalsResponse = suite.query(QueryMsg::Approvals {
+        token_id: id.to_string(),
+        include_expired: None,
+    });
+    assert_eq!(approvals.approvals.len(), 1);
+    assert_eq!(approvals.approvals[0].spender, CAROL);
+    assert_eq!(owner_of(&suite, id).approvals.len(), 1);
+
+    // a stranger cannot move it, the approved spender can
+    assert_error(
+        suite.execute(
+            BOB,
+            ExecuteMsg::TransferNft {
+                recipient: BOB.to_string(),
+                token_id: id.to_string(),
+            },
+        ),
+        ContractError::Unauthorized {},
+    );
+    let response = suite
+        .execute(
+            CAROL,
+            ExecuteMsg::TransferNft {
+                recipient: BOB.to_string(),
+                token_id: id.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        nft_event(&response, "transfer_nft"),
+        pairs(&[("action", "transfer_nft"), ("sender", CAROL), ("recipient", BOB), ("token_id", &id.to_string())])
+    );
+    let owner = owner_of(&suite, id);
+    assert_eq!(owner.owner, BOB);
+    // a transfer neither mints nor burns
+    let count: NumTokensResponse = suite.query(QueryMsg::NumTokens {});
+    assert_eq!(count.count, 1);
+    // approvals of the former owner do not follow the nft
+    assert!(owner.approvals.is_empty());
+    assert!(tokens(&suite, ALICE).is_empty());
+    assert_eq!(tokens(&suite, BOB), vec![id.to_string()]);
+    assert_eq!(suite.positions(BOB)[0].address, Addr::unchecked(BOB));
+
+    // the former owner has nothing left to take back
+    assert_error(
+        suite.execute(ALICE, ExecuteMsg::CancelPositionTransfer { id }),
+        ContractError::Unauthorized {},
+    );
+    assert_eq!(owner_of(&suite, id).owner, BOB);
+}
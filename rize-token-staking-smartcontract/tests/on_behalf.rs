@@ -0,0 +1,170 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Addr;
+use cw_multi_test::AppResponse;
+use rize_staking::constants::{DAYS_720_SECONDS, MAX_POSITIONS_PER_ADDRESS, MIN_ON_BEHALF_STAKE};
+use rize_staking::msg::{ExecuteMsg, ReceiveMsg};
+use rize_staking::ContractError;
+
+fn stake_for(suite: &mut Suite, sender: &str, amount: u128, unstake_not_before: Option<u64>) -> anyhow::Result<AppResponse> {
+    suite.send(
+        sender,
+        amount,
+        &ReceiveMsg::Stake {
+            lock_type: 0,
+            lock_seconds: None,
+            curation: None,
+            beneficiary: Some(Addr::unchecked(BOB)),
+            unstake_not_before,
+        },
+    )
+}
+
+fn authorize(suite: &mut Suite, authorized: bool) {
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::UpdateStakeFunder {
+                funder: Addr::unchecked(FUNDER),
+                authorized,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn only_allowed_funders_stake_for_others() {
+    let mut suite = Suite::new();
+    assert_error(
+        stake_for(&mut suite, FUNDER, MIN_ON_BEHALF_STAKE, None),
+        ContractError::Unauthorized {},
+    );
+
+    authorize(&mut suite, true);
+    stake_for(&mut suite, FUNDER, MIN_ON_BEHALF_STAKE, None).unwrap();
+    let position = &suite.positions(BOB)[0];
+    assert_eq!(position.funder, Some(Addr::unchecked(FUNDER)));
+
+    authorize(&mut suite, false);
+    assert_error(
+        stake_for(&mut suite, FUNDER, MIN_ON_BEHALF_STAKE, None),
+        ContractError::Unauthorized {},
+    );
+}
+
+#[test]
+fn stakes_for_others_are_not_dust() {
+    let mut suite = Suite::new();
+    authorize(&mut suite, true);
+    assert_error(
+        stake_for(&mut suite, FUNDER, MIN_ON_BEHALF_STAKE - 1, None),
+        ContractError::BelowMinimumStake {},
+    );
+    // staking for oneself has no minimum
+    suite.stake(BOB, 1, 0).unwrap();
+}
+
+#[test]
+fn unstake_not_before_is_bounded_by_the_longest_tier() {
+    let mut suite = Suite::new();
+    authorize(&mut suite, true);
+    assert_error(
+        stake_for(&mut suite, FUNDER, MIN_ON_BEHALF_STAKE, Some(u64::MAX)),
+        ContractError::InvalidInput {},
+    );
+
+    let latest = suite.now() + DAYS_720_SECONDS;
+    stake_for(&mut suite, FUNDER, MIN_ON_BEHALF_STAKE, Some(latest)).unwrap();
+    assert_eq!(suite.positions(BOB)[0].unstake_not_before, Some(latest));
+}
+
+#[test]
+fn an_address_holds_a_bounded_number_of_positions() {
+    let mut suite = Suite::new();
+    authorize(&mut suite, true);
+    for _ in 0..MAX_POSITIONS_PER_ADDRESS {
+        suite.stake(BOB, 1, 0).unwrap();
+    }
+    assert_error(
+        stake_for(&mut suite, FUNDER, MIN_ON_BEHALF_STAKE, None),
+        ContractError::TooManyPositions {},
+    );
+    assert_error(suite.stake(BOB, 1, 0), ContractError::TooManyPositions {});
+
+    // positions cannot be pushed onto a full address by transfer either
+    suite.stake(ALICE, 1, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+    assert_error(
+        suite.execute(
+            ALICE,
+            ExecuteMsg::TransferPosition {
+                id,
+                recipient: Addr::unchecked(BOB),
+            },
+        ),
+        ContractError::TooManyPositions {},
+    );
+}
+
+#[test]
+fn merging_does_not_release_a_funded_position_early() {
+    let mut suite = Suite::new();
+    authorize(&mut suite, true);
+    let not_before = suite.now() + 60 * DAY;
+    stake_for(&mut suite, FUNDER, MIN_ON_BEHALF_STAKE, Some(not_before)).unwrap();
+    suite.stake(BOB, 1, 0).unwrap();
+    let ids: Vec<u64> = suite.positions(BOB).iter().map(|position| position.id).collect();
+
+    // neither order lets the funded principal into the self-funded position
+    for order in [vec![ids[1], ids[0]], vec![ids[0], ids[1]]] {
+        assert_error(
+            suite.execute(BOB, ExecuteMsg::MergePositions { ids: order }),
+            ContractError::PositionsMismatch {},
+        );
+    }
+
+    // the tier lock is over but the funder's lock is not
+    suite.advance(31 * DAY);
+    assert_error(
+        suite.execute(
+            BOB,
+            ExecuteMsg::Unstake {
+                index: 0,
+                amount: MIN_ON_BEHALF_STAKE.into(),
+            },
+        ),
+        ContractError::StillLocked {},
+    );
+    assert_eq!(suite.positions(BOB)[0].funder, Some(Addr::unchecked(FUNDER)));
+}
+
+#[test]
+fn merged_positions_of_one_funder_keep_the_latest_unstake_not_before() {
+    let mut suite = Suite::new();
+    authorize(&mut suite, true);
+    let earlier = suite.now() + 40 * DAY;
+    let later = suite.now() + 60 * DAY;
+    stake_for(&mut suite, FUNDER, MIN_ON_BEHALF_STAKE, Some(later)).unwrap();
+    stake_for(&mut suite, FUNDER, MIN_ON_BEHALF_STAKE, Some(earlier)).unwrap();
+    let ids: Vec<u64> = suite.positions(BOB).iter().map(|position| position.id).collect();
+
+    suite
+        .execute(BOB, ExecuteMsg::MergePositions { ids: vec![ids[1], ids[0]] })
+        .unwrap();
+    let merged = &suite.positions(BOB)[0];
+    assert_eq!(merged.amount.u128(), 2 * MIN_ON_BEHALF_STAKE);
+    assert_eq!(merged.unstake_not_before, Some(later));
+
+    suite.advance(50 * DAY);
+    assert_error(
+        suite.execute(
+            BOB,
+            ExecuteMsg::Unstake {
+                index: 0,
+                amount: (2 * MIN_ON_BEHALF_STAKE).into(),
+            },
+        ),
+        ContractError::StillLocked {},
+    );
+}
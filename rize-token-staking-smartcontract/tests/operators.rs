@@ -0,0 +1,136 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Timestamp};
+use cw_utils::Expiration;
+use rize_staking::constants::DAYS_30_APY;
+use rize_staking::msg::{ExecuteMsg, OperatorPermission, OperatorGrantsResponse, QueryMsg, RewardDistribution};
+use rize_staking::ContractError;
+
+const AMOUNT: u128 = 1_000_000;
+
+fn grant(suite: &mut Suite, permissions: Vec<OperatorPermission>, expires: Option<Expiration>) {
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::GrantOperator {
+                operator: Addr::unchecked(CAROL),
+                permissions,
+                expires,
+            },
+        )
+        .unwrap();
+}
+
+fn claim_for(recipient: Option<&str>, distribution: Option<RewardDistribution>) -> ExecuteMsg {
+    ExecuteMsg::ClaimReward {
+        distribution,
+        owner: Some(Addr::unchecked(ALICE)),
+        recipient: recipient.map(Addr::unchecked),
+        ids: None,
+        max_amount: None,
+    }
+}
+
+fn compound_for() -> ExecuteMsg {
+    ExecuteMsg::Compound {
+        owner: Some(Addr::unchecked(ALICE)),
+    }
+}
+
+// alice with a matured position and a funded pool
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite.deposit_reward(AMOUNT).unwrap();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    suite.advance(31 * DAY);
+    suite
+}
+
+#[test]
+fn a_claim_operator_claims_to_the_owner_only() {
+    let mut suite = setup();
+    grant(&mut suite, vec![OperatorPermission::Claim], None);
+
+    // the reward never goes to the operator, not even through the distribution
+    assert_error(suite.execute(CAROL, claim_for(Some(CAROL), None)), ContractError::Unauthorized {});
+    let distribution = RewardDistribution {
+        charity: 10,
+        ..RewardDistribution::default()
+    };
+    assert_error(
+        suite.execute(CAROL, claim_for(None, Some(distribution))),
+        ContractError::Unauthorized {},
+    );
+    // nor may it compound
+    assert_error(suite.execute(CAROL, compound_for()), ContractError::Unauthorized {});
+
+    suite.execute(CAROL, claim_for(None, None)).unwrap();
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE - AMOUNT + reward_for(AMOUNT, DAYS_30_APY, 31 * DAY));
+    assert_eq!(suite.balance(CAROL), INITIAL_BALANCE);
+}
+
+#[test]
+fn a_compound_operator_restakes_the_reward() {
+    let mut suite = setup();
+    grant(&mut suite, vec![OperatorPermission::Compound], None);
+    assert_error(suite.execute(CAROL, claim_for(None, None)), ContractError::Unauthorized {});
+
+    let response = suite.execute(CAROL, compound_for()).unwrap();
+    let compounded: u128 = suite.attr(&response, "compounded_amount").parse().unwrap();
+    assert!(compounded > 0);
+    assert_eq!(suite.positions(ALICE)[0].amount.u128(), AMOUNT + compounded);
+    assert_eq!(suite.balance(CAROL), INITIAL_BALANCE);
+}
+
+#[test]
+fn expired_and_revoked_grants_stop_working() {
+    let mut suite = setup();
+    let expires = Expiration::AtTime(Timestamp::from_seconds(suite.now() + DAY));
+    grant(&mut suite, vec![OperatorPermission::Claim, OperatorPermission::Compound], Some(expires));
+    let operators: OperatorGrantsResponse = suite.query(QueryMsg::Operators {
+        owner: Addr::unchecked(ALICE),
+    });
+    assert_eq!(operators.operators.len(), 1);
+    assert_eq!(operators.operators[0].operator, Addr::unchecked(CAROL));
+    assert_eq!(operators.operators[0].expires, expires);
+
+    suite.advance(DAY);
+    assert_error(suite.execute(CAROL, claim_for(None, None)), ContractError::Unauthorized {});
+
+    // a fresh grant works until it is revoked
+    grant(&mut suite, vec![OperatorPermission::Claim], None);
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::RevokeOperator {
+                operator: Addr::unchecked(CAROL),
+            },
+        )
+        .unwrap();
+    assert_error(suite.execute(CAROL, claim_for(None, None)), ContractError::Unauthorized {});
+    let operators: OperatorGrantsResponse = suite.query(QueryMsg::Operators {
+        owner: Addr::unchecked(ALICE),
+    });
+    assert!(operators.operators.is_empty());
+}
+
+#[test]
+fn grants_need_permissions_and_another_address() {
+    let mut suite = Suite::new();
+    for (operator, permissions) in [(CAROL, vec![]), (ALICE, vec![OperatorPermission::Claim])] {
+        assert_error(
+            suite.execute(
+                ALICE,
+                ExecuteMsg::GrantOperator {
+                    operator: Addr::unchecked(operator),
+                    permissions,
+                    expires: None,
+                },
+            ),
+            ContractError::InvalidInput {},
+        );
+    }
+    // without a grant nobody acts for alice
+    assert_error(suite.execute(BOB, claim_for(None, None)), ContractError::Unauthorized {});
+}
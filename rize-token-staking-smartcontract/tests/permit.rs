@@ -0,0 +1,284 @@
+mod common;
+
+use anyhow::Result as AnyResult;
+use common::*;
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{
+    to_binary, Addr, Api, Binary, CanonicalAddr, RecoverPubkeyError, StdResult, Uint128, VerificationError,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_multi_test::{App, AppBuilder, AppResponse, BankKeeper, Executor};
+use k256::ecdsa::signature::DigestSigner;
+use k256::ecdsa::{Signature, SigningKey};
+use ripemd::Ripemd160;
+use rize_staking::constants::DAYS_30_APY;
+use rize_staking::msg::{
+    ExecuteMsg, InstantiateMsg, Permit, PermitAction, PermitNonceResponse, PermitPayload, QueryMsg, ReceiveMsg,
+};
+use rize_staking::ContractError;
+use sha2::{Digest, Sha256};
+
+const AMOUNT: u128 = 1_000_000;
+const FEE: u128 = 100;
+const OWNER_KEY: [u8; 32] = [7; 32];
+const OTHER_KEY: [u8; 32] = [9; 32];
+
+// the mock api with cosmos style addresses for keys, the hex of the 20 byte hash of the public key
+struct TestApi(MockApi);
+
+impl Api for TestApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        let canonical = self.addr_canonicalize(human)?;
+        self.addr_humanize(&canonical)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        match hex::decode(human) {
+            Ok(bytes) if bytes.len() == 20 => Ok(bytes.into()),
+            _ => self.0.addr_canonicalize(human),
+        }
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        if canonical.len() == 20 {
+            return Ok(Addr::unchecked(hex::encode(canonical.as_slice())));
+        }
+        self.0.addr_humanize(canonical)
+    }
+
+    fn secp256k1_verify(&self, hash: &[u8], signature: &[u8], pubkey: &[u8]) -> Result<bool, VerificationError> {
+        self.0.secp256k1_verify(hash, signature, pubkey)
+    }
+
+    fn secp256k1_recover_pubkey(&self, hash: &[u8], signature: &[u8], param: u8) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.0.secp256k1_recover_pubkey(hash, signature, param)
+    }
+
+    fn ed25519_verify(&self, message: &[u8], signature: &[u8], pubkey: &[u8]) -> Result<bool, VerificationError> {
+        self.0.ed25519_verify(message, signature, pubkey)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        pubkeys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_batch_verify(messages, signatures, pubkeys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.0.debug(message)
+    }
+}
+
+type PermitApp = App<BankKeeper, TestApi>;
+
+fn pubkey(key: &[u8]) -> Vec<u8> {
+    SigningKey::from_bytes(key).unwrap().verifying_key().to_bytes().to_vec()
+}
+
+fn key_address(key: &[u8]) -> Addr {
+    Addr::unchecked(hex::encode(Ripemd160::digest(Sha256::digest(pubkey(key)))))
+}
+
+// the staking contract with a funded pool and a matured position of the key owner
+struct PermitSuite {
+    app: PermitApp,
+    token: Addr,
+    staking: Addr,
+    owner: Addr,
+}
+
+impl PermitSuite {
+    fn new() -> Self {
+        let mut app = AppBuilder::new().with_api(TestApi(MockApi::default())).build(|_, _, _| {});
+        let owner = key_address(&OWNER_KEY);
+        let cw20_code_id = app.store_code(cw20_contract());
+        let staking_code_id = app.store_code(staking_contract());
+        let token = app
+            .instantiate_contract(
+                cw20_code_id,
+                Addr::unchecked(OWNER),
+                &cw20_base::msg::InstantiateMsg {
+                    name: "Rize".to_string(),
+                    symbol: "RIZE".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![
+                        Cw20Coin {
+                            address: OWNER.to_string(),
+                            amount: Uint128::new(INITIAL_BALANCE),
+                        },
+                        Cw20Coin {
+                            address: owner.to_string(),
+                            amount: Uint128::new(AMOUNT),
+                        },
+                    ],
+                    mint: None,
+                    marketing: None,
+                },
+                &[],
+                "rize",
+                None,
+            )
+            .unwrap();
+        let staking = app
+            .instantiate_contract(
+                staking_code_id,
+                Addr::unchecked(OWNER),
+                &InstantiateMsg {
+                    rize_token_address: token.clone(),
+                    reward_interval: DAY,
+                },
+                &[],
+                "rize-staking",
+                None,
+            )
+            .unwrap();
+
+        let mut suite = PermitSuite { app, token, staking, owner };
+        suite.send(OWNER, INITIAL_BALANCE, &ReceiveMsg::DepositReward {});
+        let owner = suite.owner.to_string();
+        suite.send(&owner, AMOUNT, &stake_msg(0));
+        suite
+            .app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                suite.staking.clone(),
+                &ExecuteMsg::UpdatePermitFeeCap { percent: 10 },
+                &[],
+            )
+            .unwrap();
+        suite.app.update_block(|block| {
+            block.time = block.time.plus_seconds(31 * DAY);
+            block.height += 31 * DAY / 5;
+        });
+        suite
+    }
+
+    fn send(&mut self, sender: &str, amount: u128, msg: &ReceiveMsg) {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.token.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: self.staking.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_binary(msg).unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    fn balance(&self, address: &Addr) -> u128 {
+        let response: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.token,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        response.balance.u128()
+    }
+
+    fn payload(&self, nonce: u64) -> PermitPayload {
+        PermitPayload {
+            contract: self.staking.clone(),
+            chain_id: self.app.block_info().chain_id,
+            owner: self.owner.clone(),
+            nonce,
+            expires: self.app.block_info().time.seconds() + DAY,
+            action: PermitAction::ClaimReward {},
+            relayer_fee: Uint128::new(FEE),
+        }
+    }
+
+    fn submit(&mut self, permit: Permit) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(BOB),
+            self.staking.clone(),
+            &ExecuteMsg::ClaimWithPermit {
+                owner: self.owner.clone(),
+                permit,
+            },
+            &[],
+        )
+    }
+}
+
+// signs the sha256 of the json of the payload, the same bytes the contract hashes
+fn sign(payload: PermitPayload, key: &[u8]) -> Permit {
+    let signing_key = SigningKey::from_bytes(key).unwrap();
+    let signature: Signature = signing_key.sign_digest(Sha256::new().chain_update(to_binary(&payload).unwrap()));
+    Permit {
+        payload,
+        pubkey: Binary::from(pubkey(key)),
+        signature: Binary::from(signature.as_ref()),
+    }
+}
+
+#[test]
+fn a_signed_permit_pays_the_owner_and_the_relayer() {
+    let mut suite = PermitSuite::new();
+    let permit = sign(suite.payload(0), &OWNER_KEY);
+    suite.submit(permit).unwrap();
+
+    assert_eq!(suite.balance(&Addr::unchecked(BOB)), FEE);
+    let reward = reward_for(AMOUNT, DAYS_30_APY, 31 * DAY);
+    assert_eq!(suite.balance(&suite.owner), reward - FEE);
+    let nonce: PermitNonceResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.staking,
+            &QueryMsg::PermitNonce {
+                owner: suite.owner.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(nonce.nonce, 1);
+}
+
+#[test]
+fn a_permit_cannot_be_replayed() {
+    let mut suite = PermitSuite::new();
+    let permit = sign(suite.payload(0), &OWNER_KEY);
+    suite.submit(permit.clone()).unwrap();
+    assert_error(suite.submit(permit), ContractError::InvalidNonce { expected: 1 });
+}
+
+#[test]
+fn an_expired_permit_is_rejected() {
+    let mut suite = PermitSuite::new();
+    let permit = sign(suite.payload(0), &OWNER_KEY);
+    suite.app.update_block(|block| block.time = block.time.plus_seconds(DAY));
+    assert_error(suite.submit(permit), ContractError::PermitExpired {});
+}
+
+#[test]
+fn a_permit_of_another_signer_is_rejected() {
+    let mut suite = PermitSuite::new();
+    // a key that is not the owner's
+    assert_error(suite.submit(sign(suite.payload(0), &OTHER_KEY)), ContractError::InvalidPermit {});
+
+    // the owner's key with a signature made by another key
+    let mut permit = sign(suite.payload(0), &OTHER_KEY);
+    permit.pubkey = Binary::from(pubkey(&OWNER_KEY));
+    assert_error(suite.submit(permit), ContractError::InvalidPermit {});
+}
+
+#[test]
+fn a_modified_payload_is_rejected() {
+    let mut suite = PermitSuite::new();
+    let mut permit = sign(suite.payload(0), &OWNER_KEY);
+    permit.payload.relayer_fee = Uint128::new(10 * FEE);
+    assert_error(suite.submit(permit), ContractError::InvalidPermit {});
+
+    // the untouched permit still works afterwards
+    let permit = sign(suite.payload(0), &OWNER_KEY);
+    suite.submit(permit).unwrap();
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use rize_staking::constants::DAYS_720_SECONDS;
+use rize_staking::msg::{ExecuteMsg, ReceiveMsg};
+use rize_staking::ContractError;
+
+const AMOUNT: u128 = 1_000_000;
+
+fn split(suite: &mut Suite, id: u64, amount: u128) -> anyhow::Result<cw_multi_test::AppResponse> {
+    suite.execute(
+        ALICE,
+        ExecuteMsg::SplitPosition {
+            id,
+            amount: Uint128::new(amount),
+        },
+    )
+}
+
+#[test]
+fn split_shares_amount_and_reward_by_amount() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+
+    // a matured extension leaves claimable reward, an early one leaves locked reward
+    suite.advance(40 * DAY);
+    suite.execute(ALICE, ExecuteMsg::ExtendLock { id, new_tier: 1 }).unwrap();
+    suite.advance(10 * DAY);
+    suite.execute(ALICE, ExecuteMsg::ExtendLock { id, new_tier: 7 }).unwrap();
+    let before = suite.positions(ALICE)[0].clone();
+    assert!(!before.reward.is_zero());
+    assert!(!before.locked_reward.is_zero());
+
+    split(&mut suite, id, AMOUNT / 4).unwrap();
+    let positions = suite.positions(ALICE);
+    let (kept, split) = (&positions[0], &positions[1]);
+    assert_ne!(split.id, id);
+    assert_eq!(kept.amount.u128(), AMOUNT - AMOUNT / 4);
+    assert_eq!(split.amount.u128(), AMOUNT / 4);
+    assert_eq!(split.reward, before.reward.multiply_ratio(1u128, 4u128));
+    assert_eq!(split.locked_reward, before.locked_reward.multiply_ratio(1u128, 4u128));
+    assert_eq!(kept.reward + split.reward, before.reward);
+    assert_eq!(kept.locked_reward + split.locked_reward, before.locked_reward);
+
+    // both halves keep the lock
+    for position in [kept, split] {
+        assert_eq!(position.lock_type, DAYS_720_SECONDS);
+        assert_eq!(position.last_time, before.last_time);
+    }
+}
+
+#[test]
+fn splitting_and_merging_pays_nothing_before_maturity() {
+    let mut suite = Suite::new();
+    suite.deposit_reward(INITIAL_BALANCE).unwrap();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+
+    for _ in 0..3 {
+        suite.advance(DAY);
+        split(&mut suite, id, AMOUNT / 2).unwrap();
+        let ids = suite.positions(ALICE).iter().map(|position| position.id).collect();
+        suite.execute(ALICE, ExecuteMsg::MergePositions { ids }).unwrap();
+        assert_error(suite.claim(ALICE), ContractError::NoReward {});
+    }
+    let position = &suite.positions(ALICE)[0];
+    assert!(position.reward.is_zero());
+    assert!(!position.locked_reward.is_zero());
+}
+
+#[test]
+fn split_rejects_nothing_everything_and_vesting_grants() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+    for amount in [0, AMOUNT, AMOUNT + 1] {
+        assert_error(split(&mut suite, id, amount), ContractError::InvalidInput {});
+    }
+    assert_eq!(suite.positions(ALICE).len(), 1);
+
+    // a grant vests as a whole, so it cannot be cut into pieces
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::UpdateVestingFunder {
+                funder: Addr::unchecked(FUNDER),
+                authorized: true,
+            },
+        )
+        .unwrap();
+    suite
+        .send(
+            FUNDER,
+            AMOUNT,
+            &ReceiveMsg::GrantVesting {
+                beneficiary: Addr::unchecked(ALICE),
+                lock_type: 0,
+                cliff: 0,
+                duration: 360 * DAY,
+            },
+        )
+        .unwrap();
+    let grant = suite.positions(ALICE)[1].id;
+    assert_error(split(&mut suite, grant, AMOUNT / 2), ContractError::InvalidInput {});
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Uint128;
+use rize_staking::constants::DAYS_720_APY;
+use rize_staking::msg::{ExecuteMsg, ReceiveMsg, TopUpPolicy};
+use rize_staking::ContractError;
+
+const AMOUNT: u128 = 1_000_000;
+
+// a 720 day position of alice under the given top up policy, with its id
+fn setup(policy: TopUpPolicy) -> (Suite, u64) {
+    let mut suite = Suite::new();
+    suite
+        .execute(OWNER, ExecuteMsg::UpdateTopUpPolicy { tier: 7, policy })
+        .unwrap();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+    (suite, id)
+}
+
+#[test]
+fn reset_settles_and_locks_again_from_now() {
+    let (mut suite, id) = setup(TopUpPolicy::Reset);
+    suite.advance(100 * DAY);
+
+    suite.send(ALICE, AMOUNT, &ReceiveMsg::IncreasePosition { id }).unwrap();
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.amount.u128(), 2 * AMOUNT);
+    assert_eq!(position.last_time, suite.now());
+    assert_eq!(position.locked_reward.u128(), settled(AMOUNT, DAYS_720_APY, 100 * DAY));
+}
+
+#[test]
+fn weighted_average_settles_and_only_moves_the_unlock_time() {
+    let (mut suite, id) = setup(TopUpPolicy::WeightedAverage);
+    let start = suite.now();
+    suite.advance(360 * DAY);
+
+    suite.send(ALICE, AMOUNT, &ReceiveMsg::IncreasePosition { id }).unwrap();
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.last_time, start + 180 * DAY);
+    assert_eq!(position.settled_time, Some(suite.now()));
+    assert_eq!(position.locked_reward.u128(), settled(AMOUNT, DAYS_720_APY, 360 * DAY));
+
+    // matured on the weighted start, paid for the time since the top up only
+    suite.advance(540 * DAY);
+    suite.deposit_reward(INITIAL_BALANCE).unwrap();
+    let balance = suite.balance(ALICE);
+    suite.claim(ALICE).unwrap();
+    assert_eq!(
+        suite.balance(ALICE) - balance,
+        settled(AMOUNT, DAYS_720_APY, 360 * DAY) + reward_for(2 * AMOUNT, DAYS_720_APY, 540 * DAY)
+    );
+}
+
+#[test]
+fn topping_up_a_long_matured_position_keeps_the_added_amount_locked() {
+    let (mut suite, id) = setup(TopUpPolicy::WeightedAverage);
+    suite.advance(1000 * DAY);
+    suite.deposit_reward(INITIAL_BALANCE).unwrap();
+
+    // the matured lock counts as unlocking now, the added half brings half a lock with it
+    suite.send(ALICE, AMOUNT, &ReceiveMsg::IncreasePosition { id }).unwrap();
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.last_time, suite.now() - 360 * DAY);
+    let unstake = ExecuteMsg::Unstake {
+        index: 0,
+        amount: Uint128::new(2 * AMOUNT),
+    };
+    assert_error(suite.execute(ALICE, unstake.clone()), ContractError::StillLocked {});
+    suite.advance(360 * DAY);
+    suite.execute(ALICE, unstake).unwrap();
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use rize_staking::constants::MAX_POSITIONS_PER_ADDRESS;
+use rize_staking::msg::{CurationTarget, ExecuteMsg, PendingTransferResponse, QueryMsg, ReceiveMsg};
+use rize_staking::ContractError;
+
+const AMOUNT: u128 = 1_000_000;
+
+fn offer(suite: &mut Suite, sender: &str, id: u64, recipient: &str) -> anyhow::Result<cw_multi_test::AppResponse> {
+    suite.execute(
+        sender,
+        ExecuteMsg::TransferPosition {
+            id,
+            recipient: Addr::unchecked(recipient),
+        },
+    )
+}
+
+fn pending(suite: &Suite, id: u64) -> Option<Addr> {
+    let response: PendingTransferResponse = suite.query(QueryMsg::PendingTransfer { id });
+    response.recipient
+}
+
+#[test]
+fn a_position_moves_once_the_recipient_accepts_it() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    let position = suite.positions(ALICE)[0].clone();
+
+    offer(&mut suite, ALICE, position.id, BOB).unwrap();
+    assert_eq!(pending(&suite, position.id), Some(Addr::unchecked(BOB)));
+    assert_eq!(suite.positions(ALICE).len(), 1);
+    assert!(suite.positions(BOB).is_empty());
+
+    // only the recipient takes the offer
+    assert_error(
+        suite.execute(CAROL, ExecuteMsg::AcceptPosition { id: position.id }),
+        ContractError::Unauthorized {},
+    );
+    suite.execute(BOB, ExecuteMsg::AcceptPosition { id: position.id }).unwrap();
+    assert!(suite.positions(ALICE).is_empty());
+    let moved = &suite.positions(BOB)[0];
+    assert_eq!(moved.id, position.id);
+    assert_eq!(moved.amount, position.amount);
+    assert_eq!(moved.last_time, position.last_time);
+    assert_eq!(pending(&suite, position.id), None);
+
+    // the offer is used up
+    assert_error(
+        suite.execute(BOB, ExecuteMsg::AcceptPosition { id: position.id }),
+        ContractError::Unauthorized {},
+    );
+}
+
+#[test]
+fn a_declined_offer_cannot_be_accepted() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+
+    // once offered only the recipient can cancel it
+    offer(&mut suite, ALICE, id, BOB).unwrap();
+    for sender in [ALICE, CAROL] {
+        assert_error(
+            suite.execute(sender, ExecuteMsg::CancelPositionTransfer { id }),
+            ContractError::Unauthorized {},
+        );
+    }
+    suite.execute(BOB, ExecuteMsg::CancelPositionTransfer { id }).unwrap();
+    assert_eq!(pending(&suite, id), None);
+    assert_error(
+        suite.execute(BOB, ExecuteMsg::AcceptPosition { id }),
+        ContractError::Unauthorized {},
+    );
+
+    // an unstaked position takes its offer with it
+    offer(&mut suite, ALICE, id, BOB).unwrap();
+    suite.advance(31 * DAY);
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::Unstake {
+                index: 0,
+                amount: Uint128::new(AMOUNT),
+            },
+        )
+        .unwrap();
+    assert_eq!(pending(&suite, id), None);
+}
+
+#[test]
+fn dust_positions_cannot_be_pushed_onto_another_address() {
+    let mut suite = Suite::new();
+    suite.execute(OWNER, ExecuteMsg::UpdateCurationShare { share: 10 }).unwrap();
+
+    // dust positions that differ in curation, so none of them could be merged away
+    for i in 0..MAX_POSITIONS_PER_ADDRESS {
+        suite
+            .send(
+                ALICE,
+                1,
+                &ReceiveMsg::Stake {
+                    lock_type: 0,
+                    lock_seconds: None,
+                    curation: Some(CurationTarget::Artist {
+                        address: Addr::unchecked(format!("artist{}", i)),
+                    }),
+                    beneficiary: None,
+                    unstake_not_before: None,
+                },
+            )
+            .unwrap();
+    }
+    let ids: Vec<u64> = suite.positions(ALICE).iter().map(|position| position.id).collect();
+    for id in ids {
+        offer(&mut suite, ALICE, id, BOB).unwrap();
+    }
+
+    // the victim holds nothing it did not accept and keeps staking and merging as before
+    assert!(suite.positions(BOB).is_empty());
+    suite.stake(BOB, AMOUNT, 0).unwrap();
+    suite.stake(BOB, AMOUNT, 0).unwrap();
+    let ids = suite.positions(BOB).iter().map(|position| position.id).collect();
+    suite.execute(BOB, ExecuteMsg::MergePositions { ids }).unwrap();
+    assert_eq!(suite.positions(BOB)[0].amount.u128(), 2 * AMOUNT);
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::AppResponse;
+use rize_staking::constants::{DAYS_30_APY, MAX_UNSTAKE_ALL_POSITIONS};
+use rize_staking::msg::{ClaimableBalanceResponse, ExecuteMsg, QueryMsg};
+
+const AMOUNT: u128 = 1_000_000;
+
+fn claimable(suite: &Suite, address: &str) -> u128 {
+    let response: ClaimableBalanceResponse = suite.query(QueryMsg::ClaimableBalance {
+        address: Addr::unchecked(address),
+    });
+    response.amount.u128()
+}
+
+fn unstake(suite: &mut Suite, amount: u128) {
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::Unstake {
+                index: 0,
+                amount: Uint128::new(amount),
+            },
+        )
+        .unwrap();
+}
+
+fn unstake_all(suite: &mut Suite) -> AppResponse {
+    suite.execute(ALICE, ExecuteMsg::UnstakeAll { claim_rewards: true }).unwrap()
+}
+
+#[test]
+fn a_partial_unstake_settles_and_keeps_the_lock() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, 2 * AMOUNT, 0).unwrap();
+    let start = suite.now();
+    suite.advance(40 * DAY);
+
+    unstake(&mut suite, AMOUNT);
+    let earned = settled(2 * AMOUNT, DAYS_30_APY, 40 * DAY);
+    assert_eq!(claimable(&suite, ALICE), earned / 2);
+    let position = &suite.positions(ALICE)[0];
+    assert_eq!(position.amount.u128(), AMOUNT);
+    assert_eq!(position.reward.u128(), earned - earned / 2);
+    assert_eq!(position.last_time, start);
+
+    // what the withdrawn part earned is paid with the rest of the reward
+    suite.deposit_reward(INITIAL_BALANCE).unwrap();
+    let balance = suite.balance(ALICE);
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE) - balance, earned);
+}
+
+#[test]
+fn every_partial_unstake_is_settled_at_the_amount_staked_then() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, 2 * AMOUNT, 0).unwrap();
+    suite.advance(40 * DAY);
+    unstake(&mut suite, AMOUNT);
+    suite.advance(10 * DAY);
+    unstake(&mut suite, AMOUNT);
+
+    let earned = settled(2 * AMOUNT, DAYS_30_APY, 40 * DAY) + settled(AMOUNT, DAYS_30_APY, 10 * DAY);
+    assert_eq!(claimable(&suite, ALICE), earned);
+    assert!(suite.positions(ALICE).is_empty());
+}
+
+#[test]
+fn unstake_all_keeps_the_reward_when_the_pool_is_empty() {
+    let mut suite = Suite::new();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    suite.advance(40 * DAY);
+
+    let response = unstake_all(&mut suite);
+    assert_eq!(suite.attr(&response, "remaining"), "0");
+    assert_eq!(suite.attr(&response, "claim_skipped"), "true");
+    assert_eq!(suite.attr(&response, "claim_skip_reason"), "Not enough Reward");
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE);
+    assert_eq!(claimable(&suite, ALICE), settled(AMOUNT, DAYS_30_APY, 40 * DAY));
+
+    suite.deposit_reward(INITIAL_BALANCE).unwrap();
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE + settled(AMOUNT, DAYS_30_APY, 40 * DAY));
+}
+
+#[test]
+fn unstake_all_claims_from_a_funded_pool() {
+    let mut suite = Suite::new();
+    suite.deposit_reward(AMOUNT).unwrap();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    suite.advance(40 * DAY);
+
+    let balance = suite.balance(ALICE);
+    let response = unstake_all(&mut suite);
+    assert_eq!(suite.attr(&response, "claim_skipped"), "false");
+    assert_eq!(suite.balance(ALICE) - balance, AMOUNT + settled(AMOUNT, DAYS_30_APY, 40 * DAY));
+    assert_eq!(claimable(&suite, ALICE), 0);
+}
+
+#[test]
+fn unstake_all_defers_the_claim_to_the_last_batch() {
+    let mut suite = Suite::new();
+    suite.deposit_reward(AMOUNT).unwrap();
+    for _ in 0..MAX_UNSTAKE_ALL_POSITIONS + 1 {
+        suite.stake(ALICE, 1_000, 0).unwrap();
+    }
+    suite.advance(40 * DAY);
+
+    let response = unstake_all(&mut suite);
+    assert_eq!(suite.attr(&response, "remaining"), "1");
+    assert_eq!(suite.attr(&response, "claim_skipped"), "true");
+    assert_eq!(suite.attr(&response, "claim_skip_reason"), "remaining positions");
+
+    let response = unstake_all(&mut suite);
+    assert_eq!(suite.attr(&response, "remaining"), "0");
+    assert_eq!(suite.attr(&response, "claim_skipped"), "false");
+}
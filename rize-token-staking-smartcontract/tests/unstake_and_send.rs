@@ -0,0 +1,82 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{to_binary, Addr, Binary, Uint128};
+use cw_multi_test::AppResponse;
+use rize_staking::msg::ExecuteMsg;
+use rize_staking::ContractError;
+
+const AMOUNT: u128 = 1_000_000;
+
+fn unstake_and_send(suite: &mut Suite, id: u64, amount: u128, contract: &Addr, msg: Binary) -> anyhow::Result<AppResponse> {
+    suite.execute(
+        ALICE,
+        ExecuteMsg::UnstakeAndSend {
+            id,
+            amount: Uint128::new(amount),
+            contract: contract.clone(),
+            msg,
+        },
+    )
+}
+
+#[test]
+fn unlocked_principal_is_sent_with_the_message() {
+    let mut suite = Suite::new();
+    let receiver = suite.instantiate_receiver();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+    suite.advance(31 * DAY);
+
+    let msg = to_binary(&"add_liquidity").unwrap();
+    unstake_and_send(&mut suite, id, AMOUNT / 4, &receiver, msg.clone()).unwrap();
+    assert_eq!(suite.balance(receiver.as_str()), AMOUNT / 4);
+    let received = suite.received(&receiver);
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].amount, Uint128::new(AMOUNT / 4));
+    assert_eq!(received[0].msg, msg);
+    // the rest stays staked and alice's wallet gets nothing
+    assert_eq!(suite.positions(ALICE)[0].amount.u128(), AMOUNT * 3 / 4);
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE - AMOUNT);
+}
+
+#[test]
+fn locked_principal_cannot_be_sent() {
+    let mut suite = Suite::new();
+    let receiver = suite.instantiate_receiver();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+
+    suite.advance(29 * DAY);
+    assert_error(
+        unstake_and_send(&mut suite, id, AMOUNT, &receiver, to_binary(&"list").unwrap()),
+        ContractError::StillLocked {},
+    );
+    // only positions of the sender can be sent
+    suite.stake(BOB, AMOUNT, 0).unwrap();
+    assert_error(
+        suite.execute(
+            BOB,
+            ExecuteMsg::UnstakeAndSend {
+                id,
+                amount: Uint128::new(AMOUNT),
+                contract: receiver.clone(),
+                msg: to_binary(&"list").unwrap(),
+            },
+        ),
+        ContractError::NoStaked {},
+    );
+}
+
+#[test]
+fn a_refusing_contract_undoes_the_unstake() {
+    let mut suite = Suite::new();
+    let receiver = suite.instantiate_receiver();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+    suite.advance(31 * DAY);
+
+    assert!(unstake_and_send(&mut suite, id, AMOUNT, &receiver, Binary::from(b"fail".to_vec())).is_err());
+    assert_eq!(suite.positions(ALICE)[0].amount.u128(), AMOUNT);
+    assert_eq!(suite.balance(receiver.as_str()), 0);
+}
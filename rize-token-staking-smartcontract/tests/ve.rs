@@ -0,0 +1,105 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use rize_staking::constants::{DAYS_30_SECONDS, DAYS_720_SECONDS};
+use rize_staking::msg::{ExecuteMsg, QueryMsg, VePowerResponse};
+
+const AMOUNT: u128 = 720_000_000;
+
+fn ve_power(suite: &Suite, address: &str, time: u64) -> u128 {
+    let response: VePowerResponse = suite.query(QueryMsg::VePower {
+        address: Addr::unchecked(address),
+        time: Some(time),
+    });
+    response.power.u128()
+}
+
+fn total_ve_power(suite: &Suite, time: u64) -> u128 {
+    let response: VePowerResponse = suite.query(QueryMsg::TotalVePower { time: Some(time) });
+    response.power.u128()
+}
+
+// unlock times count from the start of their day
+fn unlock_day(time: u64) -> u64 {
+    time / DAY * DAY
+}
+
+// amount x remaining lock / max lock
+fn expected(amount: u128, unlock: u64, time: u64) -> u128 {
+    amount * unlock_day(unlock).saturating_sub(time) as u128 / DAYS_720_SECONDS as u128
+}
+
+#[test]
+fn power_decays_linearly_until_the_unlock() {
+    let mut suite = Suite::new();
+    let start = suite.now();
+    suite.stake(ALICE, AMOUNT, 7).unwrap();
+    let unlock = start + DAYS_720_SECONDS;
+
+    assert_eq!(ve_power(&suite, ALICE, start), expected(AMOUNT, unlock, start));
+    // a quarter and half way down the lock, read ahead of time and once the time has come
+    for days in [180, 360] {
+        let time = start + days * DAY;
+        assert_eq!(ve_power(&suite, ALICE, time), expected(AMOUNT, unlock, time));
+    }
+    suite.advance(360 * DAY);
+    assert_eq!(ve_power(&suite, ALICE, suite.now()), expected(AMOUNT, unlock, suite.now()));
+    assert_eq!(ve_power(&suite, ALICE, unlock_day(unlock)), 0);
+    assert_eq!(ve_power(&suite, ALICE, unlock + DAY), 0);
+}
+
+#[test]
+fn the_total_drops_the_slope_of_each_expired_lock() {
+    let mut suite = Suite::new();
+    let start = suite.now();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    suite.stake(BOB, AMOUNT, 7).unwrap();
+    let alice_unlock = start + DAYS_30_SECONDS;
+    let bob_unlock = start + DAYS_720_SECONDS;
+
+    let time = start + 10 * DAY;
+    let alice = ve_power(&suite, ALICE, time);
+    assert_eq!(alice, expected(AMOUNT, alice_unlock, time));
+    // the total sums the remaining locks before rounding
+    let remaining = unlock_day(alice_unlock) - time + unlock_day(bob_unlock) - time;
+    assert_eq!(total_ve_power(&suite, time), AMOUNT * remaining as u128 / DAYS_720_SECONDS as u128);
+
+    // past alice's unlock only bob's lock is left
+    let time = start + 40 * DAY;
+    assert_eq!(ve_power(&suite, ALICE, time), 0);
+    assert_eq!(total_ve_power(&suite, time), expected(AMOUNT, bob_unlock, time));
+}
+
+#[test]
+fn checkpoints_keep_the_power_before_a_change() {
+    let mut suite = Suite::new();
+    let start = suite.now();
+    suite.stake(ALICE, AMOUNT, 0).unwrap();
+    let id = suite.positions(ALICE)[0].id;
+
+    suite.advance(10 * DAY);
+    let extended = suite.now();
+    suite.execute(ALICE, ExecuteMsg::ExtendLock { id, new_tier: 7 }).unwrap();
+
+    // before the extension the 30 day lock still counts, after it the new lock from now
+    let before = start + 5 * DAY;
+    assert_eq!(ve_power(&suite, ALICE, before), expected(AMOUNT, start + DAYS_30_SECONDS, before));
+    let unlock = extended + DAYS_720_SECONDS;
+    assert_eq!(ve_power(&suite, ALICE, extended), expected(AMOUNT, unlock, extended));
+    assert_eq!(total_ve_power(&suite, extended), expected(AMOUNT, unlock, extended));
+
+    // an unstake ends the power of the position, the history stays
+    suite.advance(DAYS_720_SECONDS);
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::Unstake {
+                index: 0,
+                amount: Uint128::new(AMOUNT),
+            },
+        )
+        .unwrap();
+    assert_eq!(ve_power(&suite, ALICE, suite.now()), 0);
+    assert_eq!(ve_power(&suite, ALICE, extended), expected(AMOUNT, unlock, extended));
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::AppResponse;
+use rize_staking::constants::DAYS_30_APY;
+use rize_staking::msg::{ExecuteMsg, QueryMsg, ReceiveMsg, VestingResponse};
+use rize_staking::ContractError;
+
+const TOTAL: u128 = 12_000_000;
+const CLIFF: u64 = 90 * DAY;
+const DURATION: u64 = 360 * DAY;
+
+fn grant(suite: &mut Suite, sender: &str) -> anyhow::Result<AppResponse> {
+    suite.send(
+        sender,
+        TOTAL,
+        &ReceiveMsg::GrantVesting {
+            beneficiary: Addr::unchecked(BOB),
+            lock_type: 0,
+            cliff: CLIFF,
+            duration: DURATION,
+        },
+    )
+}
+
+// a grant of TOTAL to bob from the authorized funder
+fn setup() -> (Suite, u64) {
+    let mut suite = Suite::new();
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::UpdateVestingFunder {
+                funder: Addr::unchecked(FUNDER),
+                authorized: true,
+            },
+        )
+        .unwrap();
+    grant(&mut suite, FUNDER).unwrap();
+    let id = suite.positions(BOB)[0].id;
+    (suite, id)
+}
+
+fn vesting(suite: &Suite, id: u64) -> VestingResponse {
+    suite.query(QueryMsg::Vesting { id })
+}
+
+fn unstake(suite: &mut Suite, amount: u128) -> anyhow::Result<AppResponse> {
+    suite.execute(
+        BOB,
+        ExecuteMsg::Unstake {
+            index: 0,
+            amount: Uint128::new(amount),
+        },
+    )
+}
+
+#[test]
+fn only_authorized_funders_grant_vesting() {
+    let (mut suite, id) = setup();
+    assert_error(grant(&mut suite, ALICE), ContractError::Unauthorized {});
+
+    let position = &suite.positions(BOB)[0];
+    assert_eq!(position.amount.u128(), TOTAL);
+    assert_eq!(position.funder, Some(Addr::unchecked(FUNDER)));
+    let schedule = vesting(&suite, id).schedule;
+    assert_eq!((schedule.cliff, schedule.duration), (CLIFF, DURATION));
+}
+
+#[test]
+fn nothing_is_released_before_the_cliff() {
+    let (mut suite, id) = setup();
+    // past the tier lock but not the cliff
+    suite.advance(CLIFF - DAY);
+    let response = vesting(&suite, id);
+    assert_eq!(response.vested.u128(), 0);
+    assert_eq!(response.unvested.u128(), TOTAL);
+    assert_error(unstake(&mut suite, 1), ContractError::NotVested {});
+}
+
+#[test]
+fn the_vested_part_is_released_linearly() {
+    let (mut suite, id) = setup();
+    // a quarter of the duration is the cliff, so the first quarter vests at once
+    suite.advance(CLIFF);
+    let response = vesting(&suite, id);
+    assert_eq!(response.vested.u128(), TOTAL / 4);
+    assert_eq!(response.unstakable.u128(), TOTAL / 4);
+    assert_error(unstake(&mut suite, TOTAL / 4 + 1), ContractError::NotVested {});
+    unstake(&mut suite, TOTAL / 4).unwrap();
+    assert_eq!(suite.balance(BOB), INITIAL_BALANCE + TOTAL / 4);
+
+    suite.advance(DURATION / 4);
+    let response = vesting(&suite, id);
+    assert_eq!(response.vested.u128(), TOTAL / 2);
+    assert_eq!(response.unstakable.u128(), TOTAL / 4);
+    assert_eq!(response.schedule.released.u128(), TOTAL / 4);
+
+    // after the duration the rest leaves and the position is gone
+    suite.advance(DURATION);
+    unstake(&mut suite, TOTAL * 3 / 4).unwrap();
+    assert!(suite.positions(BOB).is_empty());
+    assert_eq!(suite.balance(BOB), INITIAL_BALANCE + TOTAL);
+}
+
+#[test]
+fn a_grant_earns_the_apy_of_its_tier() {
+    let (mut suite, _) = setup();
+    suite.deposit_reward(TOTAL).unwrap();
+    suite.advance(31 * DAY);
+
+    suite.claim(BOB).unwrap();
+    assert_eq!(suite.balance(BOB), INITIAL_BALANCE + reward_for(TOTAL, DAYS_30_APY, 31 * DAY));
+    // claiming does not release any principal
+    assert_eq!(suite.positions(BOB)[0].amount.u128(), TOTAL);
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::*;
+use rize_staking::msg::{ExecuteMsg, QueryMsg, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+
+fn power(suite: &Suite, address: &str, height: u64) -> u128 {
+    let response: VotingPowerAtHeightResponse = suite.query(QueryMsg::VotingPowerAtHeight {
+        address: address.to_string(),
+        height: Some(height),
+    });
+    response.power.u128()
+}
+
+fn total_power(suite: &Suite, height: u64) -> u128 {
+    let response: TotalPowerAtHeightResponse = suite.query(QueryMsg::TotalPowerAtHeight { height: Some(height) });
+    response.power.u128()
+}
+
+#[test]
+fn past_heights_keep_the_voting_mode_they_had() {
+    let mut suite = Suite::new();
+    // a 30 day lock weighs 30 / 720 of its amount
+    suite.stake(ALICE, 12_000, 0).unwrap();
+    suite.advance(5);
+    let unweighted = suite.height();
+    assert_eq!(power(&suite, ALICE, unweighted), 12_000);
+    assert_eq!(total_power(&suite, unweighted), 12_000);
+
+    suite.execute(OWNER, ExecuteMsg::UpdateVotingWeighted { weighted: true }).unwrap();
+    suite.advance(5);
+    let weighted = suite.height();
+
+    assert_eq!(power(&suite, ALICE, unweighted), 12_000);
+    assert_eq!(total_power(&suite, unweighted), 12_000);
+    assert_eq!(power(&suite, ALICE, weighted), 500);
+    assert_eq!(total_power(&suite, weighted), 500);
+
+    suite.execute(OWNER, ExecuteMsg::UpdateVotingWeighted { weighted: false }).unwrap();
+    suite.advance(5);
+
+    assert_eq!(power(&suite, ALICE, weighted), 500);
+    assert_eq!(power(&suite, ALICE, suite.height()), 12_000);
+}